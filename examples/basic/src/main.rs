@@ -1,7 +1,6 @@
 use leptos::prelude::*;
 use leptos_toaster::{
-    provide_toasts, Theme, Toast, ToastId, ToastOptions, ToastVariant, Toaster, ToasterPosition,
-    Toasts,
+    provide_toasts, Theme, Toast, ToastId, ToastVariant, Toaster, ToasterPosition, Toasts,
 };
 
 fn main() {
@@ -11,55 +10,49 @@ fn main() {
 #[component]
 fn App() -> impl IntoView {
     provide_toasts();
+    let position = RwSignal::new(ToasterPosition::BottomRight);
+    let toast_theme = RwSignal::new(Theme::Light);
     view! {
-        <Toaster/>
-        <Page/>
+        <Toaster position theme=toast_theme/>
+        <Page position toast_theme/>
     }
 }
 
 #[component]
-fn Page() -> impl IntoView {
+fn Page(position: RwSignal<ToasterPosition>, toast_theme: RwSignal<Theme>) -> impl IntoView {
     let toast_context = expect_context::<Toasts>();
     let variant = RwSignal::new(ToastVariant::Normal);
     let use_rich_colors = RwSignal::new(false);
     let invert = RwSignal::new(false);
-    let toast_theme = RwSignal::new(Theme::Light);
     let duration = RwSignal::new(std::time::Duration::from_secs(100));
-    let position = RwSignal::new(ToasterPosition::BottomRight);
 
     let create_custom_toast = move |_| {
-        toast_context.toast(
-            || view! {
+        toast_context
+            .build(|| view! {
                 <div class="bg-gradient-to-r from-cyan-500 to-blue-500 rounded text-white p-4">"Custom toast"</div>
-            },
-            None,
-            None,
-        );
+            })
+            .show();
     };
 
     let create_toast = move |_| {
         let toast_id = ToastId::new();
-        toast_context.toast(
-            move || {
+        toast_context
+            .build(move || {
                 view! {
                     <Toast
                         toast_id
                         variant=variant()
-                        theme=toast_theme()
                         invert=invert()
                         rich_colors=use_rich_colors()
                         title=move || view! { "Title" }
                         description=Some(ViewFn::from(move || view! { "Description" }))
                     />
                 }
-            },
-            Some(toast_id),
-            Some(ToastOptions {
-                dismissible: true,
-                duration: Some(duration()),
-                position: Some(ToasterPosition::BottomLeft),
-            }),
-        );
+            })
+            .id(toast_id)
+            .variant(variant())
+            .duration(duration())
+            .show();
     };
 
     view! {
@@ -0,0 +1,83 @@
+use std::fmt::Display;
+use web_sys::KeyboardEvent;
+
+/// A keyboard shortcut, e.g. the one used to focus the Toaster
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Hotkey {
+    /// The physical key, as given by `KeyboardEvent.code`, e.g. "KeyT"
+    pub code: String,
+    pub alt: bool,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub meta: bool,
+}
+
+impl Hotkey {
+    /// A hotkey without any modifiers
+    pub fn new(code: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            alt: false,
+            ctrl: false,
+            shift: false,
+            meta: false,
+        }
+    }
+
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    pub fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    pub fn meta(mut self) -> Self {
+        self.meta = true;
+        self
+    }
+
+    pub(crate) fn matches(&self, ev: &KeyboardEvent) -> bool {
+        ev.code() == self.code
+            && ev.alt_key() == self.alt
+            && ev.ctrl_key() == self.ctrl
+            && ev.shift_key() == self.shift
+            && ev.meta_key() == self.meta
+    }
+}
+
+/// Alt+T, like sonner
+impl Default for Hotkey {
+    fn default() -> Self {
+        Hotkey::new("KeyT").alt()
+    }
+}
+
+/// Formats the hotkey like "alt+T", which is used in the aria-label of the Toaster
+impl Display for Hotkey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (pressed, modifier) in [
+            (self.ctrl, "ctrl"),
+            (self.alt, "alt"),
+            (self.shift, "shift"),
+            (self.meta, "meta"),
+        ] {
+            if pressed {
+                write!(f, "{modifier}+")?;
+            }
+        }
+        let key = self
+            .code
+            .strip_prefix("Key")
+            .or_else(|| self.code.strip_prefix("Digit"))
+            .unwrap_or(&self.code);
+        write!(f, "{key}")
+    }
+}
@@ -1,15 +1,43 @@
 #![deny(unused_crate_dependencies)]
 
+mod hotkey;
 mod mount_style;
+mod mute;
+#[cfg(feature = "builtin_toast")]
+mod persist;
+mod swipe;
+#[cfg(feature = "builtin_toast")]
+mod tab_sync;
+mod throttle;
 #[cfg(feature = "builtin_toast")]
 mod toast;
+mod toast_builder;
 mod toast_container;
+mod toast_event;
+mod toast_handle;
+mod toast_history;
 mod toast_id;
+#[cfg(feature = "builtin_toast")]
+mod toast_spec;
 mod toaster;
+mod toaster_stack;
 mod types;
 
+pub use hotkey::Hotkey;
+pub use mute::MuteSettings;
+pub use throttle::Throttle;
 #[cfg(feature = "builtin_toast")]
-pub use toast::{Theme, Toast, ToastVariant};
+pub use toast::Toast;
+pub use toast_builder::ToastBuilder;
+pub use toast_event::{ToastEvent, ToastEventListener};
+pub use toast_handle::{ToastClosed, ToastHandle};
+pub use toast_history::ToastHistory;
 pub use toast_id::ToastId;
+#[cfg(feature = "builtin_toast")]
+pub use toast_spec::ToastSpec;
 pub use toaster::{provide_toasts, Toaster};
-pub use types::{dismiss_toast, ToastOptions, ToasterPosition, Toasts};
+pub use types::{
+    dismiss_toast, DismissReason, EvictionPolicy, HistoryEntry, Politeness, Priority,
+    SwipeDirection, Theme, ToastOptions, ToastOrder, ToastSnapshot, ToastVariant, ToasterMode,
+    ToasterOffset, ToasterPosition, Toasts,
+};
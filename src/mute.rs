@@ -0,0 +1,80 @@
+use js_sys::{Array, Object, Reflect, JSON};
+use leptos::prelude::*;
+use std::{collections::BTreeSet, str::FromStr};
+use wasm_bindgen::JsValue;
+
+use crate::ToastVariant;
+
+/// Which toasts are muted. Muted toasts are not shown, but recorded so they can be looked at later,
+/// see [`Toasts::muted_toasts`]
+///
+/// [`Toasts::muted_toasts`]: crate::Toasts::muted_toasts
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct MuteSettings {
+    /// Mutes all toasts, except for critical ones
+    pub do_not_disturb: bool,
+    /// Mutes the toasts with any of these tags
+    pub tags: BTreeSet<String>,
+    /// Mutes the toasts of these variants
+    pub variants: BTreeSet<ToastVariant>,
+}
+
+impl MuteSettings {
+    /// Reads the settings saved with [`MuteSettings::save`]
+    pub(crate) fn load(storage_key: &str) -> Option<Self> {
+        let saved = window()
+            .local_storage()
+            .ok()
+            .flatten()?
+            .get_item(storage_key)
+            .ok()
+            .flatten()?;
+        let saved = JSON::parse(&saved).ok()?;
+        let strings = |key: &str| -> Vec<String> {
+            Reflect::get(&saved, &JsValue::from_str(key))
+                .ok()
+                .map(|value| Array::from(&value))
+                .map(|array| array.iter().filter_map(|v| v.as_string()).collect())
+                .unwrap_or_default()
+        };
+        Some(Self {
+            do_not_disturb: Reflect::get(&saved, &JsValue::from_str("doNotDisturb"))
+                .ok()
+                .and_then(|value| value.as_bool())
+                .unwrap_or_default(),
+            tags: strings("tags").into_iter().collect(),
+            variants: strings("variants")
+                .iter()
+                .filter_map(|variant| ToastVariant::from_str(variant).ok())
+                .collect(),
+        })
+    }
+
+    /// Saves the settings to the local storage of the browser, as JSON
+    pub(crate) fn save(&self, storage_key: &str) {
+        let Some(storage) = window().local_storage().ok().flatten() else {
+            return;
+        };
+        let saved = Object::new();
+        let strings =
+            |values: Vec<String>| values.into_iter().map(JsValue::from).collect::<Array>();
+        let _ = Reflect::set(
+            &saved,
+            &JsValue::from_str("doNotDisturb"),
+            &JsValue::from_bool(self.do_not_disturb),
+        );
+        let _ = Reflect::set(
+            &saved,
+            &JsValue::from_str("tags"),
+            &strings(self.tags.iter().cloned().collect()),
+        );
+        let _ = Reflect::set(
+            &saved,
+            &JsValue::from_str("variants"),
+            &strings(self.variants.iter().map(|v| v.to_string()).collect()),
+        );
+        if let Ok(saved) = JSON::stringify(&saved).map(String::from) {
+            let _ = storage.set_item(storage_key, &saved);
+        }
+    }
+}
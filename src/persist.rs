@@ -0,0 +1,76 @@
+use js_sys::{Array, JSON};
+use leptos::{ev, prelude::*};
+use web_sys::Storage;
+
+use crate::{toast_spec::SerializedToast, types::Toasts};
+
+/// The session storage entry the persisted toasts of a Toaster are kept in
+fn storage_key(toaster_id: Option<&str>) -> String {
+    match toaster_id {
+        Some(toaster_id) => format!("leptos_toaster:{toaster_id}"),
+        None => "leptos_toaster".to_string(),
+    }
+}
+
+fn session_storage() -> Option<Storage> {
+    window().session_storage().ok().flatten()
+}
+
+/// Shows the toasts persisted by the Toaster on the previous page, and persists its toasts again when the page is left
+pub(crate) fn persist_toasts(toasts: Toasts, toaster_id: Option<String>) {
+    let storage_key = storage_key(toaster_id.as_deref());
+    // Effects only run in the browser, where the session storage is
+    let restore_key = storage_key.clone();
+    Effect::new(move |_| untrack(|| restore(toasts, &restore_key)));
+
+    let handle = window_event_listener(ev::Custom::<web_sys::Event>::new("pagehide"), move |_| {
+        save(toasts, &storage_key, toaster_id.as_deref())
+    });
+    on_cleanup(move || handle.remove());
+}
+
+fn restore(toasts: Toasts, storage_key: &str) {
+    let Some(storage) = session_storage() else {
+        return;
+    };
+    let Ok(Some(saved)) = storage.get_item(storage_key) else {
+        return;
+    };
+    let _ = storage.remove_item(storage_key);
+    let Ok(saved) = JSON::parse(&saved) else {
+        return;
+    };
+    // The toasts are saved newest first, so the oldest is shown first to keep them in the same order
+    let saved = Array::from(&saved)
+        .iter()
+        .filter_map(|toast| SerializedToast::from_js(&toast))
+        .collect::<Vec<_>>();
+    // The other tabs already know about the restored toasts, and timed them out themselves
+    crate::tab_sync::receiving(toasts, || {
+        for toast in saved.into_iter().rev() {
+            let mut options = toast.options();
+            options.persist = true;
+            toasts.toast_from_spec(toast.spec, Some(toast.id), Some(options));
+        }
+    });
+}
+
+fn save(toasts: Toasts, storage_key: &str, toaster_id: Option<&str>) {
+    let Some(storage) = session_storage() else {
+        return;
+    };
+    let now = js_sys::Date::now();
+    let saved = toasts.toasts.with_untracked(|toasts| {
+        toasts
+            .iter()
+            .filter(|t| t.options.persist && t.options.is_for_toaster(toaster_id))
+            .filter_map(|t| SerializedToast::new(t, now))
+            .map(|t| t.to_js())
+            .collect::<Array>()
+    });
+    if saved.length() == 0 {
+        let _ = storage.remove_item(storage_key);
+    } else if let Ok(saved) = JSON::stringify(&saved) {
+        let _ = storage.set_item(storage_key, &String::from(saved));
+    }
+}
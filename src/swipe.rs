@@ -0,0 +1,307 @@
+use crate::types::SwipeDirection;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Axis {
+    X,
+    Y,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// How far or fast a toast has to be swiped before it is dismissed
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct SwipeThresholds {
+    /// In pixels
+    pub distance: f64,
+    /// In pixels per millisecond
+    pub velocity: f64,
+}
+
+/// The state of a swipe gesture on a toast.
+///
+/// A gesture goes from `Idle` to `Pressed` when the pointer goes down, to `Swiping` once the pointer has moved far
+/// enough to lock the axis, and ends either `Released` far or fast enough to dismiss the toast, or back in `Idle` when
+/// it is cancelled or released too early. Timestamps are in milliseconds.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum SwipeState {
+    Idle,
+    Pressed {
+        start: Point,
+        pressed_at: f64,
+    },
+    Swiping {
+        start: Point,
+        pressed_at: f64,
+        axis: Axis,
+        amount: f64,
+    },
+    Released {
+        direction: SwipeDirection,
+        amount: f64,
+    },
+}
+
+impl SwipeState {
+    pub fn press(self, at: Point, time: f64) -> Self {
+        match self {
+            SwipeState::Idle => SwipeState::Pressed {
+                start: at,
+                pressed_at: time,
+            },
+            _ => self,
+        }
+    }
+
+    /// Follows the pointer. The axis is locked once the pointer has moved more than `start_distance`, so a swipe only
+    /// goes one way, and the gesture is cancelled if the toast can't be swiped along that axis at all
+    pub fn move_to(self, at: Point, start_distance: f64, directions: &[SwipeDirection]) -> Self {
+        let (start, pressed_at, axis) = match self {
+            SwipeState::Pressed { start, pressed_at } => {
+                let (delta_x, delta_y) = (at.x - start.x, at.y - start.y);
+                if delta_x.abs().max(delta_y.abs()) <= start_distance {
+                    return self;
+                }
+                let axis = if delta_x.abs() > delta_y.abs() {
+                    Axis::X
+                } else {
+                    Axis::Y
+                };
+                (start, pressed_at, axis)
+            }
+            SwipeState::Swiping {
+                start,
+                pressed_at,
+                axis,
+                ..
+            } => (start, pressed_at, axis),
+            SwipeState::Idle | SwipeState::Released { .. } => return self,
+        };
+
+        let delta = match axis {
+            Axis::X => at.x - start.x,
+            Axis::Y => at.y - start.y,
+        };
+        let (direction, opposite) = direction_of(axis, delta);
+        let amount = if directions.contains(&direction) {
+            delta
+        } else if directions.contains(&opposite) {
+            // Let the toast follow the pointer a bit, so it is clear that it can be swiped, just not this way
+            delta / (1.5 + delta.abs() / 20.0)
+        } else {
+            return SwipeState::Idle;
+        };
+
+        SwipeState::Swiping {
+            start,
+            pressed_at,
+            axis,
+            amount,
+        }
+    }
+
+    /// Ends the gesture, which dismisses the toast if it was swiped far or fast enough in an allowed direction
+    pub fn release(
+        self,
+        time: f64,
+        directions: &[SwipeDirection],
+        thresholds: SwipeThresholds,
+    ) -> Self {
+        let SwipeState::Swiping {
+            pressed_at,
+            axis,
+            amount,
+            ..
+        } = self
+        else {
+            return match self {
+                SwipeState::Released { .. } => self,
+                _ => SwipeState::Idle,
+            };
+        };
+
+        let (direction, _) = direction_of(axis, amount);
+        let time_taken = time - pressed_at;
+        let velocity = if time_taken > 0.0 {
+            amount.abs() / time_taken
+        } else {
+            0.0
+        };
+
+        if directions.contains(&direction)
+            && (amount.abs() >= thresholds.distance || velocity > thresholds.velocity)
+        {
+            SwipeState::Released { direction, amount }
+        } else {
+            SwipeState::Idle
+        }
+    }
+
+    /// Aborts the gesture, e.g. when the browser takes over the pointer
+    pub fn cancel(self) -> Self {
+        match self {
+            SwipeState::Released { .. } => self,
+            _ => SwipeState::Idle,
+        }
+    }
+
+    /// Whether the pointer is down on the toast, or the toast is being swiped out
+    pub fn is_active(&self) -> bool {
+        !matches!(self, SwipeState::Idle)
+    }
+
+    pub fn swiped_out(&self) -> Option<SwipeDirection> {
+        match self {
+            SwipeState::Released { direction, .. } => Some(*direction),
+            _ => None,
+        }
+    }
+
+    /// How far the toast has been swiped along the axis
+    pub fn amount(&self, along: Axis) -> f64 {
+        match *self {
+            SwipeState::Swiping { axis, amount, .. } if axis == along => amount,
+            SwipeState::Released { direction, amount } => {
+                let axis = match direction {
+                    SwipeDirection::Left | SwipeDirection::Right => Axis::X,
+                    SwipeDirection::Up | SwipeDirection::Down => Axis::Y,
+                };
+                if axis == along {
+                    amount
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// The direction of a movement along the axis, and its opposite
+fn direction_of(axis: Axis, delta: f64) -> (SwipeDirection, SwipeDirection) {
+    match axis {
+        Axis::X if delta > 0.0 => (SwipeDirection::Right, SwipeDirection::Left),
+        Axis::X => (SwipeDirection::Left, SwipeDirection::Right),
+        Axis::Y if delta > 0.0 => (SwipeDirection::Down, SwipeDirection::Up),
+        Axis::Y => (SwipeDirection::Up, SwipeDirection::Down),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLDS: SwipeThresholds = SwipeThresholds {
+        distance: 20.0,
+        velocity: 0.11,
+    };
+    const START_DISTANCE: f64 = 2.0;
+    const ALL: [SwipeDirection; 4] = [
+        SwipeDirection::Up,
+        SwipeDirection::Down,
+        SwipeDirection::Left,
+        SwipeDirection::Right,
+    ];
+
+    fn at(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    fn swipe_to(to: Point, directions: &[SwipeDirection]) -> SwipeState {
+        SwipeState::Idle
+            .press(at(0.0, 0.0), 0.0)
+            .move_to(to, START_DISTANCE, directions)
+    }
+
+    #[test]
+    fn release_past_distance_threshold() {
+        let state = swipe_to(at(0.0, 30.0), &ALL).release(1000.0, &ALL, THRESHOLDS);
+        assert_eq!(
+            state,
+            SwipeState::Released {
+                direction: SwipeDirection::Down,
+                amount: 30.0
+            }
+        );
+        assert_eq!(state.swiped_out(), Some(SwipeDirection::Down));
+    }
+
+    #[test]
+    fn release_past_velocity_threshold() {
+        // 10px in 50ms is 0.2px/ms, fast enough even though it isn't far enough
+        let state = swipe_to(at(-10.0, 0.0), &ALL).release(50.0, &ALL, THRESHOLDS);
+        assert_eq!(
+            state,
+            SwipeState::Released {
+                direction: SwipeDirection::Left,
+                amount: -10.0
+            }
+        );
+    }
+
+    #[test]
+    fn release_too_early() {
+        let state = swipe_to(at(10.0, 0.0), &ALL).release(1000.0, &ALL, THRESHOLDS);
+        assert_eq!(state, SwipeState::Idle);
+    }
+
+    #[test]
+    fn release_without_time_passing() {
+        let short = swipe_to(at(10.0, 0.0), &ALL).release(0.0, &ALL, THRESHOLDS);
+        assert_eq!(short, SwipeState::Idle);
+
+        let long = swipe_to(at(30.0, 0.0), &ALL).release(0.0, &ALL, THRESHOLDS);
+        assert_eq!(long.swiped_out(), Some(SwipeDirection::Right));
+    }
+
+    #[test]
+    fn waits_for_start_distance() {
+        let state = swipe_to(at(1.0, 1.0), &ALL);
+        assert!(matches!(state, SwipeState::Pressed { .. }));
+        assert!(state.is_active());
+    }
+
+    #[test]
+    fn locks_axis() {
+        let state = swipe_to(at(10.0, 3.0), &ALL);
+        assert_eq!(state.amount(Axis::X), 10.0);
+
+        // Moving mostly vertically afterwards still only moves the toast horizontally
+        let state = state.move_to(at(5.0, 40.0), START_DISTANCE, &ALL);
+        assert_eq!(state.amount(Axis::X), 5.0);
+        assert_eq!(state.amount(Axis::Y), 0.0);
+    }
+
+    #[test]
+    fn disallowed_axis_cancels() {
+        let state = swipe_to(at(30.0, 0.0), &[SwipeDirection::Down]);
+        assert_eq!(state, SwipeState::Idle);
+    }
+
+    #[test]
+    fn dampens_opposite_direction() {
+        let directions = [SwipeDirection::Down];
+        let state = swipe_to(at(0.0, -30.0), &directions);
+        assert_eq!(state.amount(Axis::Y), -30.0 / (1.5 + 30.0 / 20.0));
+
+        // Released in the opposite direction, the toast stays
+        let state = state.release(10.0, &directions, THRESHOLDS);
+        assert_eq!(state, SwipeState::Idle);
+    }
+
+    #[test]
+    fn cancel_from_every_state() {
+        let pressed = SwipeState::Idle.press(at(0.0, 0.0), 0.0);
+        let swiping = swipe_to(at(10.0, 0.0), &ALL);
+        let released = swipe_to(at(30.0, 0.0), &ALL).release(100.0, &ALL, THRESHOLDS);
+
+        assert_eq!(SwipeState::Idle.cancel(), SwipeState::Idle);
+        assert_eq!(pressed.cancel(), SwipeState::Idle);
+        assert_eq!(swiping.cancel(), SwipeState::Idle);
+        // A toast that is already being swiped out keeps going
+        assert_eq!(released.cancel(), released);
+    }
+}
@@ -0,0 +1,136 @@
+use js_sys::{Object, Reflect};
+use leptos::prelude::*;
+use std::collections::HashSet;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{BroadcastChannel, MessageEvent};
+
+use crate::{
+    toast_spec::SerializedToast,
+    types::{Toast, Toasts},
+    ToastHandle, ToastId,
+};
+
+/// The channel toasts are shared with other tabs through, see [`Toasts::sync_across_tabs`]
+pub(crate) struct TabSync {
+    channel: BroadcastChannel,
+    /// Kept around for as long as the channel is open
+    _on_message: Closure<dyn Fn(MessageEvent)>,
+    /// Set while showing or dismissing a toast from another tab, or restoring a persisted one, so it isn't sent back
+    receiving: bool,
+    /// Toasts dismissed in another tab. The toast is animated out before it is closed, so it isn't sent back once it is
+    dismissed_elsewhere: HashSet<ToastId>,
+}
+
+pub(crate) fn connect(toasts: Toasts, channel_name: &str) {
+    let Ok(channel) = BroadcastChannel::new(channel_name) else {
+        return;
+    };
+    let on_message =
+        Closure::<dyn Fn(MessageEvent)>::new(move |ev: MessageEvent| receive(toasts, &ev.data()));
+    channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    toasts.tab_sync.set_value(Some(TabSync {
+        channel,
+        _on_message: on_message,
+        receiving: false,
+        dismissed_elsewhere: HashSet::new(),
+    }));
+    on_cleanup(move || {
+        if let Some(tab_sync) = toasts.tab_sync.try_update_value(Option::take).flatten() {
+            tab_sync.channel.close();
+        }
+    });
+}
+
+/// Shows the toast in the other tabs, if it was created from a spec
+pub(crate) fn send_shown(toasts: Toasts, toast: &Toast) {
+    if toast.spec.is_none() {
+        return;
+    }
+    let Some(serialized) = SerializedToast::new(toast, js_sys::Date::now()) else {
+        return;
+    };
+    send(toasts, "shown", "toast", serialized.to_js());
+}
+
+/// Dismisses the toast in the other tabs, if it was created from a spec and not dismissed in one of them
+pub(crate) fn send_dismissed(toasts: Toasts, toast: &Toast) {
+    let dismissed_elsewhere = toasts
+        .tab_sync
+        .try_update_value(|tab_sync| {
+            tab_sync
+                .as_mut()
+                .is_some_and(|tab_sync| tab_sync.dismissed_elsewhere.remove(&toast.id))
+        })
+        .unwrap_or_default();
+    if toast.spec.is_some() && !dismissed_elsewhere {
+        send(
+            toasts,
+            "dismissed",
+            "id",
+            toast.id.to_decodable_string().into(),
+        );
+    }
+}
+
+fn send(toasts: Toasts, kind: &str, key: &str, value: JsValue) {
+    toasts.tab_sync.with_value(|tab_sync| {
+        let Some(tab_sync) = tab_sync.as_ref().filter(|tab_sync| !tab_sync.receiving) else {
+            return;
+        };
+        let message = Object::new();
+        let _ = Reflect::set(&message, &"type".into(), &kind.into());
+        let _ = Reflect::set(&message, &key.into(), &value);
+        let _ = tab_sync.channel.post_message(&message);
+    });
+}
+
+fn receive(toasts: Toasts, message: &JsValue) {
+    let get = |key: &str| Reflect::get(message, &key.into()).ok();
+    match get("type").and_then(|kind| kind.as_string()).as_deref() {
+        Some("shown") => {
+            let Some(toast) = get("toast").and_then(|toast| SerializedToast::from_js(&toast))
+            else {
+                return;
+            };
+            // The toasts are keyed by their id, so a toast that is already here isn't shown twice
+            if toasts.find(&toast.id, |_| ()).is_some() {
+                return;
+            }
+            receiving(toasts, || {
+                toasts.toast_from_spec(toast.spec.clone(), Some(toast.id), Some(toast.options()))
+            });
+        }
+        Some("dismissed") => {
+            let Some(id) = get("id").and_then(|id| id.as_string()) else {
+                return;
+            };
+            let id = ToastId::decode_string(&id);
+            if toasts.find(&id, |_| ()).is_none() {
+                return;
+            }
+            toasts.tab_sync.update_value(|tab_sync| {
+                if let Some(tab_sync) = tab_sync {
+                    tab_sync.dismissed_elsewhere.insert(id);
+                }
+            });
+            receiving(toasts, || ToastHandle::new(id, toasts).dismiss());
+        }
+        _ => {}
+    }
+}
+
+/// Runs the function without sending the toasts it shows or dismisses to the other tabs
+pub(crate) fn receiving<T>(toasts: Toasts, f: impl FnOnce() -> T) -> T {
+    set_receiving(toasts, true);
+    let result = f();
+    set_receiving(toasts, false);
+    result
+}
+
+fn set_receiving(toasts: Toasts, receiving: bool) {
+    toasts.tab_sync.update_value(|tab_sync| {
+        if let Some(tab_sync) = tab_sync {
+            tab_sync.receiving = receiving;
+        }
+    });
+}
@@ -0,0 +1,230 @@
+use leptos::prelude::*;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use crate::{types::Summary, ToastId};
+
+/// Limits how many toasts with the same throttle key are shown within a time window, see [`Toasts::set_throttle`].
+/// Toasts beyond the limit are dropped, unless they are summarized in a single toast
+///
+/// [`Toasts::set_throttle`]: crate::Toasts::set_throttle
+#[derive(Clone)]
+pub struct Throttle {
+    max_toasts: usize,
+    window: Duration,
+    summary: Option<Summary>,
+}
+
+impl Throttle {
+    /// Show at most `max_toasts` toasts per throttle key within the window
+    pub fn new(max_toasts: usize, window: Duration) -> Self {
+        Self {
+            max_toasts,
+            window,
+            summary: None,
+        }
+    }
+
+    /// Instead of dropping the toasts beyond the limit, show a single toast with the view created from how many toasts
+    /// were throttled, e.g. "12 more errors"
+    pub fn summarize<F, V>(mut self, summary: F) -> Self
+    where
+        F: Fn(usize) -> V + Clone + Send + Sync + 'static,
+        V: IntoView + 'static,
+    {
+        self.summary = Some(Arc::new(move |throttled| {
+            let summary = summary.clone();
+            ViewFn::from(move || summary(throttled))
+        }));
+        self
+    }
+
+    pub(crate) fn summary_view(&self, throttled: usize) -> Option<ViewFn> {
+        self.summary.as_ref().map(|summary| summary(throttled))
+    }
+}
+
+/// The time since the Unix epoch, used to tell when a throttle window has passed and to timestamp the history
+pub(crate) type Clock = Arc<dyn Fn() -> Duration + Send + Sync>;
+
+/// The time of the browser, or of the system when there is no browser, e.g. on the server
+pub(crate) fn system_clock() -> Clock {
+    #[cfg(target_arch = "wasm32")]
+    return Arc::new(|| Duration::from_secs_f64(js_sys::Date::now() / 1000.0));
+    #[cfg(not(target_arch = "wasm32"))]
+    Arc::new(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+    })
+}
+
+/// What to do with a toast that has a throttle key
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum ThrottleDecision {
+    Show,
+    /// The toast is over the limit, and this many toasts have been throttled in the window so far
+    Throttle(usize),
+}
+
+#[derive(Clone, Debug)]
+struct ThrottleWindow {
+    started_at: Duration,
+    shown: usize,
+    throttled: usize,
+    summary: Option<ToastId>,
+}
+
+/// The throttle windows of the keys that recently had toasts
+#[derive(Default, Debug)]
+pub(crate) struct ThrottledToasts {
+    windows: HashMap<String, ThrottleWindow>,
+}
+
+impl ThrottledToasts {
+    /// Counts a toast with the key, starting a new window if the last one has passed
+    pub fn check(&mut self, key: &str, now: Duration, throttle: &Throttle) -> ThrottleDecision {
+        self.windows
+            .retain(|_, window| now.saturating_sub(window.started_at) < throttle.window);
+        let window = self
+            .windows
+            .entry(key.to_string())
+            .or_insert_with(|| ThrottleWindow {
+                started_at: now,
+                shown: 0,
+                throttled: 0,
+                summary: None,
+            });
+        if window.shown < throttle.max_toasts {
+            window.shown += 1;
+            ThrottleDecision::Show
+        } else {
+            window.throttled += 1;
+            ThrottleDecision::Throttle(window.throttled)
+        }
+    }
+
+    /// The toast summarizing the throttled toasts of the key in the current window
+    pub fn summary(&self, key: &str) -> Option<ToastId> {
+        self.windows.get(key).and_then(|window| window.summary)
+    }
+
+    pub fn set_summary(&mut self, key: &str, summary: ToastId) {
+        if let Some(window) = self.windows.get_mut(key) {
+            window.summary = Some(summary);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::DismissReason, Toasts};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn throttles_beyond_the_limit() {
+        let throttle = Throttle::new(2, secs(10));
+        let mut throttled = ThrottledToasts::default();
+        assert_eq!(
+            throttled.check("a", secs(0), &throttle),
+            ThrottleDecision::Show
+        );
+        assert_eq!(
+            throttled.check("a", secs(1), &throttle),
+            ThrottleDecision::Show
+        );
+        // The count of throttled toasts feeds the summary
+        assert_eq!(
+            throttled.check("a", secs(2), &throttle),
+            ThrottleDecision::Throttle(1)
+        );
+        assert_eq!(
+            throttled.check("a", secs(3), &throttle),
+            ThrottleDecision::Throttle(2)
+        );
+    }
+
+    #[test]
+    fn counts_per_key() {
+        let throttle = Throttle::new(1, secs(10));
+        let mut throttled = ThrottledToasts::default();
+        assert_eq!(
+            throttled.check("a", secs(0), &throttle),
+            ThrottleDecision::Show
+        );
+        assert_eq!(
+            throttled.check("b", secs(0), &throttle),
+            ThrottleDecision::Show
+        );
+        assert_eq!(
+            throttled.check("a", secs(1), &throttle),
+            ThrottleDecision::Throttle(1)
+        );
+        assert_eq!(
+            throttled.check("b", secs(1), &throttle),
+            ThrottleDecision::Throttle(1)
+        );
+    }
+
+    #[test]
+    fn window_expires() {
+        let throttle = Throttle::new(1, secs(10));
+        let mut throttled = ThrottledToasts::default();
+        assert_eq!(
+            throttled.check("a", secs(0), &throttle),
+            ThrottleDecision::Show
+        );
+        assert_eq!(
+            throttled.check("a", secs(9), &throttle),
+            ThrottleDecision::Throttle(1)
+        );
+        throttled.set_summary("a", ToastId::from_usize(1));
+        assert_eq!(throttled.summary("a"), Some(ToastId::from_usize(1)));
+
+        // The window started with the first toast, so a new one starts after 10 seconds with a new summary
+        assert_eq!(
+            throttled.check("a", secs(10), &throttle),
+            ThrottleDecision::Show
+        );
+        assert_eq!(throttled.summary("a"), None);
+        assert_eq!(
+            throttled.check("a", secs(11), &throttle),
+            ThrottleDecision::Throttle(1)
+        );
+    }
+
+    #[test]
+    fn toasts_use_the_injected_clock() {
+        let toasts = Toasts::new();
+        let now = Arc::new(AtomicU64::new(0));
+        let clock = now.clone();
+        toasts.set_clock(move || Duration::from_secs(clock.load(Ordering::SeqCst)));
+        toasts.set_throttle(Some(Throttle::new(1, secs(10))));
+        let show = || {
+            toasts
+                .build(|| "Request failed")
+                .throttle_key("request-failed")
+                .show()
+                .id()
+        };
+        let shown = |id: ToastId| {
+            toasts
+                .toasts
+                .with_untracked(|toasts| toasts.iter().any(|t| t.id == id))
+        };
+
+        assert!(shown(show()));
+        let throttled = show();
+        assert!(!shown(throttled));
+        let history = toasts.history();
+        assert_eq!(history[0].id, throttled);
+        assert_eq!(history[0].reason, DismissReason::Throttled);
+
+        now.store(10, Ordering::SeqCst);
+        assert!(shown(show()));
+    }
+}
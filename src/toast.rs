@@ -1,4 +1,8 @@
-use crate::{mount_style::mount_style, types::dismiss_toast, ToastId};
+use crate::{
+    mount_style::mount_style,
+    types::{dismiss_toast, Theme, ToasterTheme},
+    ToastId, ToastVariant,
+};
 use leptos::{either::EitherOf5, prelude::*};
 
 #[component]
@@ -77,42 +81,6 @@ fn ErrorIcon() -> impl IntoView {
     }
 }
 
-#[derive(PartialEq, Clone, Copy)]
-pub enum ToastVariant {
-    Normal,
-    Success,
-    Info,
-    Warning,
-    Error,
-}
-
-impl ToString for ToastVariant {
-    fn to_string(&self) -> String {
-        match self {
-            ToastVariant::Normal => "normal".to_string(),
-            ToastVariant::Success => "success".to_string(),
-            ToastVariant::Info => "info".to_string(),
-            ToastVariant::Warning => "warning".to_string(),
-            ToastVariant::Error => "error".to_string(),
-        }
-    }
-}
-
-#[derive(Clone)]
-pub enum Theme {
-    Light,
-    Dark,
-}
-
-impl std::fmt::Display for Theme {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Theme::Light => write!(f, "light"),
-            Theme::Dark => write!(f, "dark"),
-        }
-    }
-}
-
 /// Built in toast component to use with the toast() function if you don't want to roll your own
 #[component]
 pub fn Toast(
@@ -121,7 +89,9 @@ pub fn Toast(
     #[prop(default = None,into)] description: Option<ViewFn>,
     toast_id: ToastId,
     #[prop(default = true)] close_button: bool,
-    #[prop(default = Theme::Light)] theme: Theme,
+    /// Defaults to the theme of the Toaster
+    #[prop(optional, into)]
+    theme: Option<Signal<Theme>>,
     #[prop(default = false)] invert: bool,
     #[prop(default = false)] rich_colors: bool,
 ) -> impl IntoView {
@@ -129,11 +99,14 @@ pub fn Toast(
         "leptos-toaster-builtin_toast",
         include_str!("./builtin_toast.css"),
     );
+    let theme = theme
+        .or_else(|| use_context::<ToasterTheme>().map(|ToasterTheme(theme)| theme))
+        .unwrap_or_else(|| Theme::Light.into());
 
     view! {
         <div
             data-type=variant.to_string()
-            data-theme=theme.to_string()
+            data-theme=move || theme.get().to_string()
             data-invert=invert.to_string()
             data-rich-colors=rich_colors.to_string()
             class="leptos-toast"
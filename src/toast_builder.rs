@@ -0,0 +1,157 @@
+#[cfg(feature = "builtin_toast")]
+use crate::ToastSpec;
+use crate::{
+    types::{DismissReason, Politeness, Priority, SwipeDirection, ToastOptions, Toasts},
+    ToastHandle, ToastId, ToastVariant, ToasterPosition,
+};
+use leptos::prelude::*;
+use std::time::Duration;
+
+/// Builder for a toast, created with [`Toasts::build`]
+#[must_use = "the toast is only shown once `show` is called"]
+pub struct ToastBuilder {
+    toasts: Toasts,
+    content: Content,
+    id: Option<ToastId>,
+    options: ToastOptions,
+}
+
+enum Content {
+    View(ViewFn),
+    #[cfg(feature = "builtin_toast")]
+    Spec(ToastSpec),
+}
+
+impl ToastBuilder {
+    pub(crate) fn new(toasts: Toasts, view: ViewFn) -> Self {
+        Self {
+            toasts,
+            content: Content::View(view),
+            id: None,
+            options: ToastOptions::default(),
+        }
+    }
+
+    #[cfg(feature = "builtin_toast")]
+    pub(crate) fn from_spec(toasts: Toasts, spec: ToastSpec) -> Self {
+        Self {
+            toasts,
+            content: Content::Spec(spec),
+            id: None,
+            options: ToastOptions::default(),
+        }
+    }
+
+    /// Use the given id instead of a generated one
+    pub fn id(mut self, id: ToastId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Duration until the toast should be dismissed, overriding the one set on the Toaster
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.options.duration = Some(duration);
+        self
+    }
+
+    /// Position of the toast, overriding the one set on the Toaster
+    pub fn position(mut self, position: ToasterPosition) -> Self {
+        self.options.position = Some(position);
+        self
+    }
+
+    /// Show the toast in the Toaster with the given id instead of the one without an id
+    pub fn toaster(mut self, toaster_id: impl Into<String>) -> Self {
+        self.options.toaster_id = Some(toaster_id.into());
+        self
+    }
+
+    /// Whether the toast can be swiped away
+    pub fn dismissible(mut self, dismissible: bool) -> Self {
+        self.options.dismissible = dismissible;
+        self
+    }
+
+    /// The directions the toast can be swiped away in, overriding the ones set on the Toaster
+    pub fn swipe_directions(
+        mut self,
+        directions: impl IntoIterator<Item = SwipeDirection>,
+    ) -> Self {
+        self.options.swipe_directions = Some(directions.into_iter().collect());
+        self
+    }
+
+    /// In a Toaster in snackbar mode, dismiss the shown toast and show this one right away instead of queueing it
+    pub fn replace_current(mut self) -> Self {
+        self.options.replace_current = true;
+        self
+    }
+
+    /// How important the toast is compared to the others
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.options.priority = priority;
+        self
+    }
+
+    /// Bump the count on a shown or queued toast with the same key, instead of showing another one
+    pub fn dedupe_key(mut self, dedupe_key: impl Into<String>) -> Self {
+        self.options.dedupe_key = Some(dedupe_key.into());
+        self
+    }
+
+    /// Limit how many toasts with the same key are shown, see [`Toasts::set_throttle`]
+    pub fn throttle_key(mut self, throttle_key: impl Into<String>) -> Self {
+        self.options.throttle_key = Some(throttle_key.into());
+        self
+    }
+
+    /// Add the toast to a group, so it can be dismissed together with the other toasts in it
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.options.tags.push(tag.into());
+        self
+    }
+
+    /// Keep the toast across page loads in the same tab, see [`ToastOptions::persist`]
+    pub fn persist(mut self) -> Self {
+        self.options.persist = true;
+        self
+    }
+
+    /// Called with the reason once the toast has been dismissed
+    pub fn on_dismiss(
+        mut self,
+        on_dismiss: impl Fn(DismissReason) + Send + Sync + 'static,
+    ) -> Self {
+        self.options.on_dismiss = Some(Callback::new(on_dismiss));
+        self
+    }
+
+    /// The kind of toast, which decides how urgently it is announced to screen readers
+    pub fn variant(mut self, variant: ToastVariant) -> Self {
+        self.options.variant = variant;
+        self
+    }
+
+    /// How urgently screen readers should announce the toast, overriding the one picked from the variant
+    pub fn politeness(mut self, politeness: Politeness) -> Self {
+        self.options.politeness = Some(politeness);
+        self
+    }
+
+    /// Replace all the options at once
+    pub fn options(mut self, options: ToastOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Show the toast
+    pub fn show(self) -> ToastHandle {
+        match self.content {
+            Content::View(view) => self.toasts.toast(view, self.id, Some(self.options)),
+            #[cfg(feature = "builtin_toast")]
+            Content::Spec(spec) => self
+                .toasts
+                .toast_from_spec(spec, self.id, Some(self.options)),
+        }
+    }
+}
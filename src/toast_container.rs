@@ -1,33 +1,50 @@
 use crate::{
-    types::{decode_message, HeightT, Toast},
+    swipe::{Axis, Point, SwipeState, SwipeThresholds},
+    toast_event::ToastEvent,
+    types::{
+        decode_message, Announcements, DismissReason, HeightT, Priority, SwipeDirection, Toast,
+        ToastOrder,
+    },
     ToastId, ToasterPosition,
 };
 use js_sys::Date;
-use leptos::{ev, leptos_dom::helpers::TimeoutHandle, prelude::*};
-use std::cmp::{max, min};
-use std::time::Duration;
+use leptos::{ev, html, leptos_dom::helpers::TimeoutHandle, prelude::*};
+use std::{collections::HashMap, time::Duration};
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlElement, PointerEvent};
+use web_sys::{HtmlElement, KeyboardEvent, PointerEvent};
 
 #[component]
 pub fn ToastContainer(
     index: Signal<usize>,
     toast: Toast,
-    duration_from_toaster: Duration,
-    visible_toasts: usize,
+    duration_from_toaster: Signal<Duration>,
+    visible_toasts: Signal<usize>,
     position: ToasterPosition,
-    #[prop(into)] remove_toast: Callback<ToastId>,
+    swipe_directions_from_toaster: Option<Vec<SwipeDirection>>,
+    swipe_thresholds: SwipeThresholds,
+    mouse_swipe: bool,
+    #[prop(into)] remove_toast: Callback<(ToastId, DismissReason)>,
+    on_event: Callback<ToastEvent>,
+    /// Keeps track of which toasts are mounted, so toasts that aren't can be dismissed without animating them
+    mounted_toasts: RwSignal<HashMap<ToastId, usize>>,
+    /// Where the toast is read out by screen readers once it is shown
+    announcements: Announcements,
     expanded: ReadSignal<bool>,
-    expand_by_default: bool,
+    /// Whether the timer dismissing the toast should be paused, e.g. while the user reads the toasts
+    paused: Signal<bool>,
+    expand_by_default: Signal<bool>,
     num_toasts: Signal<usize>,
     heights: RwSignal<Vec<HeightT>>,
-    gap: usize,
+    gap: Signal<usize>,
+    order: Signal<ToastOrder>,
+    /// Whether the toast should make way for another one, as only one toast is shown in snackbar mode
+    replaced: Signal<bool>,
 ) -> impl IntoView {
     let mounted = RwSignal::new(false);
     let removed = RwSignal::new(false);
-    let swiping = RwSignal::new(false);
-    let swipe_out = RwSignal::new(false);
-    let is_visible = move || index.get() < visible_toasts;
+    // Critical toasts are never hidden, even when there are more of them than visible toasts
+    let is_visible =
+        move || index.get() < visible_toasts.get() || toast.options.priority == Priority::Critical;
     let is_front = move || index.get() == 0;
     let height_index = move || {
         heights.with(|heights| {
@@ -46,38 +63,45 @@ pub fn ToastContainer(
             acc
         })
     };
-    let offset = move || (height_index() * gap) as f64 + toasts_height_before();
-    let is_expanded = move || expanded.get() || (expand_by_default && mounted.get());
-    let duration = toast.options.duration.unwrap_or(duration_from_toaster);
-    let position = toast.options.position.unwrap_or(position);
+    let offset = move || (height_index() * gap.get()) as f64 + toasts_height_before();
+    let is_expanded = move || expanded.get() || (expand_by_default.get() && mounted.get());
+    let politeness = toast.options.effective_politeness();
+    let swipe_directions = toast
+        .options
+        .swipe_directions
+        .clone()
+        .or(swipe_directions_from_toaster)
+        .unwrap_or_else(|| position.default_swipe_directions());
 
     let initial_height = RwSignal::new(0.0);
     let offset_before_remove = RwSignal::new(0.0);
 
+    let toast_ref = NodeRef::<html::Li>::new();
+
     Effect::new(move |_| {
-        if let Some(document) = window().document() {
-            if let Ok(Some(toast_container_node)) =
-                document.query_selector(".leptos-toast-container")
-            {
-                let height = toast_container_node.get_bounding_client_rect().height();
-                initial_height.set(height);
-                heights.update(|heights| {
-                    heights.insert(
-                        0,
-                        HeightT {
-                            toast_id: toast.id,
-                            height,
-                        },
-                    )
-                });
-            }
+        if let Some(toast_container_node) = toast_ref.get() {
+            let height = toast_container_node.get_bounding_client_rect().height();
+            initial_height.set(height);
+            let height = HeightT {
+                toast_id: toast.id,
+                height,
+            };
+            // New toasts are either put in front or at the back of the stack
+            heights.update(|heights| match order.get_untracked() {
+                ToastOrder::NewestFirst => heights.insert(0, height),
+                ToastOrder::OldestFirst => heights.push(height),
+            });
         }
     });
 
     let delete_timeout_handle = RwSignal::<Option<TimeoutHandle>>::new(None);
 
-    let delete_toast = move || {
+    // Kept in a stored value so delete_toast stays Copy
+    let closing = StoredValue::new(toast.closing.clone());
+    let delete_toast = move |reason: DismissReason| {
         removed.set(true);
+        // Raising the toast again while it is animated out shows a new one, rather than bumping the one that's leaving
+        closing.with_value(|closing| closing.set_value(true));
         offset_before_remove.set(offset());
         heights.update(|heights| {
             if let Some(i) = heights.iter().position(|t| t.toast_id == toast.id) {
@@ -87,50 +111,122 @@ pub fn ToastContainer(
 
         set_timeout(
             move || {
-                // If the toast was deleted by the close button, we stop the timeout that would otherwise delete the toast a time im the future when it already has been disposed
-                if let Some(handle) = delete_timeout_handle.get() {
+                // If the toast was deleted by the close button, we stop the timeout that would otherwise delete the toast a time im the future when it already has been disposed.
+                // The container itself might have been disposed in the meantime as well
+                if let Some(handle) = delete_timeout_handle.try_get_untracked().flatten() {
                     handle.clear();
                 }
-                remove_toast.run(toast.id);
+                remove_toast.try_run((toast.id, reason));
             },
             Duration::from_millis(200),
         );
     };
 
     // The close button calls a window.postMessage which we then pick up here and delete the toast if the ids match
-    window_event_listener(ev::message, move |ev| {
+    let message_handle = window_event_listener(ev::message, move |ev| {
         if let Some(id) = ev.data().as_string() {
             if let Some(id) = decode_message(id) {
                 if id == toast.id {
-                    delete_toast();
+                    delete_toast(DismissReason::Dismissed);
                 }
             }
         }
     });
+    let timer = toast.timer.clone();
+    on_cleanup(move || {
+        message_handle.remove();
+        // Evicted toasts are unmounted without being deleted first, so their timer is still running and their height is
+        // still there. Stopping the timer keeps the time it ran, in case the toast is mounted again in another stack
+        if let Some(handle) = delete_timeout_handle.try_get_untracked().flatten() {
+            handle.clear();
+        }
+        timer.update_value(|timer| timer.stop(Date::now()));
+        mounted_toasts.try_update(|mounted| {
+            if let Some(containers) = mounted.get_mut(&toast.id) {
+                *containers -= 1;
+                if *containers == 0 {
+                    mounted.remove(&toast.id);
+                }
+            }
+        });
+        heights.try_update(|heights| heights.retain(|h| h.toast_id != toast.id));
+        announcements.withdraw(&toast.id);
+    });
 
+    let was_shown = toast.was_shown.clone();
     Effect::new(move |_| {
         mounted.set(true);
+        mounted_toasts.update(|mounted| *mounted.entry(toast.id).or_default() += 1);
+        if !was_shown.get_value() {
+            was_shown.set_value(true);
+            if let Some(text) = toast_ref.get_untracked().and_then(|li| li.text_content()) {
+                announcements.announce(toast.id, politeness, text);
+            }
+            on_event.run(ToastEvent::Shown(toast.id));
+        }
+    });
+
+    Effect::new(move |previous: Option<bool>| {
+        let paused = paused.get();
+        match previous {
+            Some(false) if paused => on_event.run(ToastEvent::Paused(toast.id)),
+            Some(true) if !paused => on_event.run(ToastEvent::Resumed(toast.id)),
+            _ => {}
+        }
+        paused
     });
 
     Effect::new(move |_| {
-        if let Ok(handle) = set_timeout_with_handle(delete_toast, duration) {
+        if replaced.get() && !removed.get_untracked() {
+            delete_toast(DismissReason::Replaced);
+        }
+    });
+
+    let count: Signal<usize> = toast.count.clone().into();
+
+    Effect::new(move |previous_count: Option<usize>| {
+        let duration = toast
+            .options
+            .duration
+            .unwrap_or_else(|| duration_from_toaster.get());
+        let paused = paused.get();
+        let count = count.get();
+
+        // Stop the running timer, and start a new one with whatever is left of the (possibly changed) duration
+        if let Some(handle) = delete_timeout_handle.get_untracked() {
+            handle.clear();
+            delete_timeout_handle.set(None);
+        }
+        let timer = toast.timer.clone();
+        timer.update_value(|timer| {
+            timer.stop(Date::now());
+            timer.duration = Some(duration);
+            // Raising the toast again restarts its timer
+            if previous_count.is_some_and(|previous_count| previous_count != count) {
+                timer.elapsed = Duration::ZERO;
+            }
+        });
+        if paused || removed.get_untracked() {
+            return count;
+        }
+        if let Ok(handle) = set_timeout_with_handle(
+            move || delete_toast(DismissReason::Timeout),
+            duration.saturating_sub(timer.with_value(|timer| timer.elapsed)),
+        ) {
+            timer.update_value(|timer| timer.started_at = Some(Date::now()));
             delete_timeout_handle.set(Some(handle));
         }
+        count
     });
 
-    #[derive(Clone)]
-    struct Point {
-        x: i32,
-        y: i32,
-    }
-    let drag_start_time = RwSignal::<Option<Date>, LocalStorage>::new_local(None);
-    let pointer_start = RwSignal::<Option<Point>>::new(None);
-    let swipe_amount = RwSignal::<i32>::new(0);
+    let gesture = RwSignal::new(SwipeState::Idle);
+    let swipe_directions = StoredValue::new(swipe_directions);
+    let can_swipe_with = move |ev: &PointerEvent| mouse_swipe || ev.pointer_type() != "mouse";
     let handle_pointerdown = move |ev: PointerEvent| {
-        if !toast.options.dismissible {
+        // Without capturing the pointer the mouse can be used to select text in the toast
+        if !toast.options.dismissible || !can_swipe_with(&ev) {
             return;
         }
-        drag_start_time.set(Some(Date::new_0()));
         offset_before_remove.set(offset());
 
         if let Some(target) = ev.target() {
@@ -139,71 +235,97 @@ pub fn ToastContainer(
                 if element.tag_name() == "BUTTON" {
                     return;
                 }
-                swiping.set(true);
-                pointer_start.set(Some(Point {
-                    x: ev.client_x(),
-                    y: ev.client_y(),
-                }));
+                gesture.update(|gesture| {
+                    *gesture = gesture.press(
+                        Point {
+                            x: ev.client_x() as f64,
+                            y: ev.client_y() as f64,
+                        },
+                        Date::now(),
+                    )
+                });
             }
         }
     };
 
     let handle_pointerup = move |_| {
-        if swipe_out.get() || !toast.options.dismissible {
+        if !toast.options.dismissible {
             return;
         }
-        pointer_start.set(None);
-        let time_taken = Date::new_0().get_time()
-            - drag_start_time.with(|t| t.as_ref().map(|t| t.get_time()).unwrap_or(0.0));
-        let velocity = swipe_amount.with(|a| a.abs() as f64) / time_taken;
-
-        if swipe_amount.with(|a| a.abs() >= 20) || velocity > 0.11 {
-            offset_before_remove.set(offset());
-            delete_toast();
-            swipe_out.set(true);
+        let released = gesture.get_untracked();
+        if released.swiped_out().is_some() {
             return;
-        };
+        }
+        let released = swipe_directions
+            .with_value(|directions| released.release(Date::now(), directions, swipe_thresholds));
+        if released.swiped_out().is_some() {
+            offset_before_remove.set(offset());
+            delete_toast(DismissReason::Swipe);
+        }
+        gesture.set(released);
+    };
 
-        swipe_amount.set(0);
-        swiping.set(false);
+    let handle_pointercancel = move |_| {
+        gesture.update(|gesture| *gesture = gesture.cancel());
     };
 
     let handle_pointermove = move |ev: PointerEvent| {
-        if !toast.options.dismissible {
+        if !toast.options.dismissible || !can_swipe_with(&ev) {
             return;
         };
-        let _pointer_start = if let Some(pointer_start) = pointer_start.get() {
-            pointer_start
-        } else {
+        let current = gesture.get_untracked();
+        if !matches!(
+            current,
+            SwipeState::Pressed { .. } | SwipeState::Swiping { .. }
+        ) {
             return;
+        }
+        let swipe_start_distance = if ev.pointer_type() == "touch" {
+            10.0
+        } else {
+            2.0
         };
+        let moved = swipe_directions.with_value(|directions| {
+            current.move_to(
+                Point {
+                    x: ev.client_x() as f64,
+                    y: ev.client_y() as f64,
+                },
+                swipe_start_distance,
+                directions,
+            )
+        });
+        gesture.set(moved);
+    };
 
-        let y_position = ev.client_y() - _pointer_start.y;
-        let x_position = ev.client_x() - _pointer_start.x;
-
-        let clamped_y = match position {
-            ToasterPosition::TopLeft | ToasterPosition::TopCenter | ToasterPosition::TopRight => {
-                min(0, y_position)
-            }
-            ToasterPosition::BottomRight
-            | ToasterPosition::BottomCenter
-            | ToasterPosition::BottomLeft => max(0, y_position),
-        };
-        let swipe_start_threshold = if ev.pointer_type() == "touch" { 10 } else { 2 };
-        let is_allowed_to_swipe = clamped_y.abs() > swipe_start_threshold;
-
-        if is_allowed_to_swipe {
-            swipe_amount.set(y_position);
-        } else if x_position.abs() > swipe_start_threshold {
-            pointer_start.set(None);
+    let handle_keydown = move |ev: KeyboardEvent| {
+        if !toast.options.dismissible {
+            return;
+        }
+        match ev.key().as_str() {
+            "Escape" => {}
+            // Delete might be meant for an input inside the toast
+            "Delete" if ev.target() == ev.current_target() => {}
+            _ => return,
+        }
+        ev.prevent_default();
+        ev.stop_propagation();
+        // Keep the focus inside the toaster, so the next toast can be reached with the arrow keys
+        if let Some(list) = ev
+            .current_target()
+            .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+            .and_then(|element| element.parent_element())
+            .and_then(|parent| parent.dyn_into::<HtmlElement>().ok())
+        {
+            let _ = list.focus();
         }
+        delete_toast(DismissReason::Dismissed);
     };
 
     view! {
         <li
-            aria-atomic="true"
-            role="status"
-            tab-index=0
+            node_ref=toast_ref
+            tabindex=0
             class="leptos-toast-container"
             data-mounted=move || mounted.get().to_string()
             data-removed=move || removed.get().to_string()
@@ -212,10 +334,12 @@ pub fn ToastContainer(
             data-x-position=position.x()
             data-index=index
             data-front=move || is_front().to_string()
-            data-swiping=move || swiping.get().to_string()
-            data-swipe-out=move || swipe_out.get().to_string()
+            data-swiping=move || gesture.with(|g| g.is_active()).to_string()
+            data-swipe-out=move || gesture.with(|g| g.swiped_out().is_some()).to_string()
+            data-swipe-direction=move || gesture.with(|g| g.swiped_out()).map(|d| d.to_string())
             data-expanded=move || is_expanded().to_string()
             data-dismissible=toast.options.dismissible.to_string()
+            data-mouse-swipe=mouse_swipe.to_string()
             style=("--index", move || index.get().to_string())
             style=("--toasts-before", move || index.get().to_string())
             style=("--z-index", move || (num_toasts.get() - index.get()).to_string())
@@ -223,19 +347,27 @@ pub fn ToastContainer(
             style=(
                 "--initial-height",
                 move || {
-                    if expand_by_default {
+                    if expand_by_default.get() {
                         "auto".to_string()
                     } else {
                         format!("{}px", initial_height.get())
                     }
                 },
             )
-            style=("--swipe-amount", move || format!("{}px", swipe_amount.get()))
+            style=("--swipe-amount-x", move || format!("{}px", gesture.with(|g| g.amount(Axis::X))))
+            style=("--swipe-amount-y", move || format!("{}px", gesture.with(|g| g.amount(Axis::Y))))
+            on:keydown=handle_keydown
             on:pointerdown=handle_pointerdown
             on:pointerup=handle_pointerup
             on:pointermove=handle_pointermove
+            on:pointercancel=handle_pointercancel
+            on:lostpointercapture=handle_pointercancel
         >
-            {toast.view.run()}
+            {move || toast.view.get().run()}
+            {move || {
+                let count = count.get();
+                (count > 1).then(|| view! { <span class="leptos-toast-count">{format!("×{count}")}</span> })
+            }}
         </li>
     }
 }
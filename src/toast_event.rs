@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::{
+    types::{DismissReason, Toasts},
+    ToastId, ToasterPosition,
+};
+
+/// Something that happened to a toast, see [`Toasts::on_event`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum ToastEvent {
+    /// The toast was mounted in a Toaster
+    Shown(ToastId),
+    /// The content of the toast was replaced, or it was raised again with the same dedupe key
+    Updated(ToastId),
+    /// The stack of toasts at the position was expanded or collapsed
+    Expanded {
+        toaster_id: Option<String>,
+        position: ToasterPosition,
+        expanded: bool,
+    },
+    /// The timer of the toast was paused, e.g. because the user hovers the toasts
+    Paused(ToastId),
+    Resumed(ToastId),
+    /// The toast was removed. Muted, throttled and evicted toasts are dismissed without ever being shown, so they emit
+    /// this without a [`ToastEvent::Shown`] before it
+    Dismissed {
+        id: ToastId,
+        reason: DismissReason,
+    },
+}
+
+pub(crate) type Listener = Arc<dyn Fn(&ToastEvent) + Send + Sync>;
+
+#[derive(Default)]
+pub(crate) struct Listeners {
+    next_id: usize,
+    listeners: Vec<(usize, Listener)>,
+}
+
+impl Listeners {
+    pub fn add(&mut self, listener: Listener) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.listeners.push((id, listener));
+        id
+    }
+
+    pub fn remove(&mut self, id: usize) {
+        self.listeners.retain(|(listener_id, _)| *listener_id != id);
+    }
+
+    pub fn all(&self) -> Vec<Listener> {
+        self.listeners
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect()
+    }
+}
+
+/// Returned by [`Toasts::on_event`], to stop listening again
+#[derive(Clone, Copy)]
+pub struct ToastEventListener {
+    pub(crate) id: usize,
+    pub(crate) toasts: Toasts,
+}
+
+impl ToastEventListener {
+    pub fn remove(self) {
+        self.toasts.remove_listener(self.id);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    toast_event::ToastEvent,
+    types::{dismiss_toast, DismissReason, Toasts},
+    ToastId,
+};
+use leptos::prelude::*;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+/// How many dismissal reasons are remembered for toasts that are no longer shown and that no [`ToastClosed`] waits for
+const MAX_CLOSED_TOASTS: usize = 100;
+
+/// Keeps track of how toasts were closed, so [`ToastHandle::closed`] can resolve
+#[derive(Default)]
+pub(crate) struct ClosedToasts {
+    reasons: VecDeque<(ToastId, DismissReason)>,
+    /// The futures waiting for a toast to close, by their key, with the waker they were last polled with
+    waiting: Vec<(usize, ToastId, Option<Waker>)>,
+    next_key: usize,
+}
+
+impl ClosedToasts {
+    pub(crate) fn close(&mut self, toast_id: ToastId, reason: DismissReason) {
+        self.forget(&toast_id);
+        if self.reasons.len() >= MAX_CLOSED_TOASTS {
+            // The reasons of toasts a future waits for are kept, however late it is polled
+            let waiting = &self.waiting;
+            if let Some(oldest) = self
+                .reasons
+                .iter()
+                .position(|(id, _)| !waiting.iter().any(|(_, waiting_id, _)| waiting_id == id))
+            {
+                self.reasons.remove(oldest);
+            }
+        }
+        self.reasons.push_back((toast_id, reason));
+
+        for (_, id, waker) in &mut self.waiting {
+            if *id == toast_id {
+                if let Some(waker) = waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+
+    fn wait(&mut self, toast_id: ToastId) -> usize {
+        let key = self.next_key;
+        self.next_key += 1;
+        self.waiting.push((key, toast_id, None));
+        key
+    }
+
+    fn set_waker(&mut self, key: usize, new_waker: &Waker) {
+        if let Some((_, _, waker)) = self.waiting.iter_mut().find(|(k, _, _)| *k == key) {
+            if !waker
+                .as_ref()
+                .is_some_and(|waker| waker.will_wake(new_waker))
+            {
+                *waker = Some(new_waker.clone());
+            }
+        }
+    }
+
+    fn stop_waiting(&mut self, key: usize) {
+        self.waiting.retain(|(k, _, _)| *k != key);
+    }
+
+    pub(crate) fn forget(&mut self, toast_id: &ToastId) {
+        self.reasons.retain(|(id, _)| id != toast_id);
+    }
+
+    fn reason(&self, toast_id: &ToastId) -> Option<DismissReason> {
+        self.reasons
+            .iter()
+            .find(|(id, _)| id == toast_id)
+            .map(|(_, reason)| *reason)
+    }
+}
+
+/// A handle to a toast, returned when creating it with [`Toasts::toast`]
+#[derive(Clone, Copy)]
+pub struct ToastHandle {
+    id: ToastId,
+    toasts: Toasts,
+}
+
+impl ToastHandle {
+    pub(crate) fn new(id: ToastId, toasts: Toasts) -> Self {
+        Self { id, toasts }
+    }
+
+    /// The id of the toast
+    pub fn id(&self) -> ToastId {
+        self.id
+    }
+
+    /// Dismiss the toast, animating it out like the close button does
+    pub fn dismiss(&self) {
+        if untrack(|| self.toasts.is_mounted(&self.id)) {
+            dismiss_toast(&self.id);
+        } else {
+            // Queued toasts are not mounted, so there is nothing to animate
+            self.toasts.dismiss(&self.id);
+        }
+    }
+
+    /// Replace the content of the toast while it is shown
+    pub fn update(&self, toast: impl Into<ViewFn>) {
+        if let Some(view) = self.toasts.find(&self.id, |t| t.view.clone()) {
+            view.set(toast.into());
+            self.toasts.emit(ToastEvent::Updated(self.id));
+        }
+    }
+
+    /// Whether the toast is shown, i.e. it is neither queued nor dismissed
+    pub fn is_visible(&self) -> Signal<bool> {
+        let Self { id, toasts } = *self;
+        Signal::derive(move || toasts.is_mounted(&id))
+    }
+
+    /// Resolves with the reason once the toast has been dismissed
+    pub fn closed(&self) -> ToastClosed {
+        let key = self
+            .toasts
+            .closed
+            .try_update_value(|closed| closed.wait(self.id));
+        ToastClosed {
+            id: self.id,
+            toasts: self.toasts,
+            key,
+        }
+    }
+}
+
+/// Future returned by [`ToastHandle::closed`]
+pub struct ToastClosed {
+    id: ToastId,
+    toasts: Toasts,
+    /// Set while the future waits for the toast, unless the toasts were disposed already
+    key: Option<usize>,
+}
+
+impl Future for ToastClosed {
+    type Output = DismissReason;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let id = self.id;
+        let is_shown = self
+            .toasts
+            .toasts
+            .with_untracked(|toasts| toasts.iter().any(|t| t.id == id));
+        let reason = self.toasts.closed.with_value(|closed| closed.reason(&id));
+        match reason {
+            Some(reason) if !is_shown => Poll::Ready(reason),
+            _ => {
+                if let Some(key) = self.key {
+                    self.toasts
+                        .closed
+                        .update_value(|closed| closed.set_waker(key, cx.waker()));
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for ToastClosed {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            // The toasts might have been disposed before the future
+            self.toasts
+                .closed
+                .try_update_value(|closed| closed.stop_waiting(key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll(closed: &mut ToastClosed) -> Poll<DismissReason> {
+        Pin::new(closed).poll(&mut Context::from_waker(Waker::noop()))
+    }
+
+    #[test]
+    fn polling_again_keeps_one_waker() {
+        let toasts = Toasts::new();
+        let handle = toasts.toast(|| "Saved", None, None);
+        let mut closed = handle.closed();
+        for _ in 0..10 {
+            assert_eq!(poll(&mut closed), Poll::Pending);
+        }
+        assert_eq!(toasts.closed.with_value(|closed| closed.waiting.len()), 1);
+
+        toasts.dismiss(&handle.id());
+        assert_eq!(poll(&mut closed), Poll::Ready(DismissReason::Dismissed));
+        drop(closed);
+        assert!(toasts.closed.with_value(|closed| closed.waiting.is_empty()));
+    }
+
+    #[test]
+    fn reasons_are_kept_for_futures_polled_late() {
+        let toasts = Toasts::new();
+        let handle = toasts.toast(|| "Saved", None, None);
+        let mut closed = handle.closed();
+        toasts.dismiss(&handle.id());
+        for _ in 0..MAX_CLOSED_TOASTS * 2 {
+            let id = toasts.toast(|| "Saved", None, None).id();
+            toasts.dismiss(&id);
+        }
+        assert_eq!(poll(&mut closed), Poll::Ready(DismissReason::Dismissed));
+        assert!(toasts.closed.with_value(|closed| closed.reasons.len()) <= MAX_CLOSED_TOASTS);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{mount_style::mount_style, types::Toasts};
+use js_sys::Date;
+use leptos::prelude::*;
+use std::time::Duration;
+use wasm_bindgen::JsValue;
+
+/// Lists the toasts that are no longer shown, so users can look at what they missed.
+/// Has to be rendered inside the Toaster, or after calling [`provide_toasts`](crate::provide_toasts)
+#[component]
+pub fn ToastHistory(
+    /// Mark the toasts as read once the history is shown
+    #[prop(optional)]
+    mark_read_on_mount: bool,
+    /// Shown when there are no toasts in the history
+    #[prop(into, default = "No notifications".to_string())]
+    empty_text: String,
+) -> impl IntoView {
+    mount_style("toast-history", include_str!("./toast_history.css"));
+    let toasts = expect_context::<Toasts>();
+    if mark_read_on_mount {
+        toasts.mark_history_read();
+    }
+    let history = move || toasts.history();
+
+    view! {
+        <section class="leptos-toast-history">
+            <header class="leptos-toast-history-header">
+                <span class="leptos-toast-history-unread" data-unread=move || toasts.unread_count()>
+                    {move || format!("{} unread", toasts.unread_count())}
+                </span>
+                <button
+                    class="leptos-toast-history-mark-read"
+                    disabled=move || toasts.unread_count() == 0
+                    on:click=move |_| toasts.mark_history_read()
+                >
+                    "Mark as read"
+                </button>
+                <button
+                    class="leptos-toast-history-clear"
+                    disabled=move || history().is_empty()
+                    on:click=move |_| toasts.clear_history()
+                >
+                    "Clear"
+                </button>
+            </header>
+            <Show
+                when=move || !history().is_empty()
+                fallback=move || view! { <p class="leptos-toast-history-empty">{empty_text.clone()}</p> }
+            >
+                <ol class="leptos-toast-history-list">
+                    <For
+                        each=history
+                        // Entries are rendered again when they are marked as read
+                        key=|entry| (entry.id, entry.dismissed_at, entry.read)
+                        children=|entry| {
+                            let dismissed_at = date(entry.dismissed_at);
+                            view! {
+                                <li
+                                    class="leptos-toast-history-entry"
+                                    data-read=entry.read.to_string()
+                                    data-variant=entry.variant.to_string()
+                                    data-reason=entry.reason.to_string()
+                                >
+                                    <div class="leptos-toast-history-content">{entry.view.run()}</div>
+                                    <time datetime=String::from(dismissed_at.to_iso_string())>
+                                        {String::from(dismissed_at.to_locale_time_string("default"))}
+                                    </time>
+                                </li>
+                            }
+                        }
+                    />
+                </ol>
+            </Show>
+        </section>
+    }
+}
+
+fn date(time: Duration) -> Date {
+    Date::new(&JsValue::from_f64(time.as_secs_f64() * 1000.0))
+}
@@ -0,0 +1,227 @@
+use js_sys::{Array, Object, Reflect};
+use leptos::prelude::*;
+use std::{str::FromStr, time::Duration};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    types::{Politeness, Priority, SwipeDirection, Toast, ToastOptions},
+    ToastId, ToastVariant, ToasterPosition,
+};
+
+/// The content of a toast as data, rendered by the built in Toast.
+/// Unlike a view, it can be stored and sent to other tabs, see [`ToastOptions::persist`](crate::ToastOptions::persist)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ToastSpec {
+    pub title: String,
+    pub description: Option<String>,
+    pub close_button: bool,
+    pub invert: bool,
+    pub rich_colors: bool,
+}
+
+impl ToastSpec {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            description: None,
+            close_button: true,
+            invert: false,
+            rich_colors: false,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn close_button(mut self, close_button: bool) -> Self {
+        self.close_button = close_button;
+        self
+    }
+
+    pub fn invert(mut self) -> Self {
+        self.invert = true;
+        self
+    }
+
+    pub fn rich_colors(mut self) -> Self {
+        self.rich_colors = true;
+        self
+    }
+}
+
+impl ToastSpec {
+    pub(crate) fn view(&self, toast_id: ToastId, variant: ToastVariant) -> ViewFn {
+        let spec = self.clone();
+        ViewFn::from(move || {
+            let title = spec.title.clone();
+            let description = spec
+                .description
+                .clone()
+                .map(|description| ViewFn::from(move || description.clone()));
+            view! {
+                <crate::Toast
+                    toast_id
+                    variant
+                    title=move || title.clone()
+                    description
+                    close_button=spec.close_button
+                    invert=spec.invert
+                    rich_colors=spec.rich_colors
+                />
+            }
+        })
+    }
+}
+
+/// A toast created from a spec, as a JS object that can be stored or sent to other tabs.
+/// The `on_dismiss` callback and `replace_current` are not kept, the first can't be serialized and the second only
+/// matters when the toast is first shown
+#[derive(Clone, Debug)]
+pub(crate) struct SerializedToast {
+    pub id: ToastId,
+    pub spec: ToastSpec,
+    pub variant: ToastVariant,
+    pub position: Option<ToasterPosition>,
+    pub toaster_id: Option<String>,
+    pub dismissible: bool,
+    pub politeness: Option<Politeness>,
+    pub swipe_directions: Option<Vec<SwipeDirection>>,
+    pub priority: Priority,
+    pub dedupe_key: Option<String>,
+    pub throttle_key: Option<String>,
+    pub tags: Vec<String>,
+    /// The time the toast has left, if it was shown
+    pub remaining: Option<Duration>,
+}
+
+impl SerializedToast {
+    pub fn new(toast: &Toast, now: f64) -> Option<Self> {
+        Some(Self {
+            id: toast.id,
+            spec: toast.spec.clone()?,
+            variant: toast.options.variant,
+            position: toast.options.position,
+            toaster_id: toast.options.toaster_id.clone(),
+            dismissible: toast.options.dismissible,
+            politeness: toast.options.politeness,
+            swipe_directions: toast.options.swipe_directions.clone(),
+            priority: toast.options.priority,
+            dedupe_key: toast.options.dedupe_key.clone(),
+            throttle_key: toast.options.throttle_key.clone(),
+            tags: toast.options.tags.clone(),
+            remaining: toast.timer.with_value(|timer| timer.remaining(now)),
+        })
+    }
+
+    /// The options to show the toast with again. The timer picks up where it left off
+    pub fn options(&self) -> ToastOptions {
+        ToastOptions {
+            variant: self.variant,
+            position: self.position,
+            toaster_id: self.toaster_id.clone(),
+            dismissible: self.dismissible,
+            politeness: self.politeness,
+            swipe_directions: self.swipe_directions.clone(),
+            priority: self.priority,
+            dedupe_key: self.dedupe_key.clone(),
+            throttle_key: self.throttle_key.clone(),
+            tags: self.tags.clone(),
+            duration: self.remaining,
+            ..ToastOptions::default()
+        }
+    }
+
+    pub fn to_js(&self) -> JsValue {
+        let object = Object::new();
+        let set = |key: &str, value: JsValue| {
+            let _ = Reflect::set(&object, &JsValue::from_str(key), &value);
+        };
+        set("id", self.id.to_decodable_string().into());
+        set("title", self.spec.title.clone().into());
+        set("description", self.spec.description.clone().into());
+        set("closeButton", self.spec.close_button.into());
+        set("invert", self.spec.invert.into());
+        set("richColors", self.spec.rich_colors.into());
+        set("variant", self.variant.to_string().into());
+        set("position", self.position.map(position_name).into());
+        set("toasterId", self.toaster_id.clone().into());
+        set("dismissible", self.dismissible.into());
+        set("politeness", self.politeness.map(|p| p.to_string()).into());
+        set(
+            "swipeDirections",
+            self.swipe_directions
+                .as_ref()
+                .map(|directions| strings(directions.iter().map(|d| d.to_string())))
+                .into(),
+        );
+        set("priority", self.priority.to_string().into());
+        set("dedupeKey", self.dedupe_key.clone().into());
+        set("throttleKey", self.throttle_key.clone().into());
+        set("tags", strings(self.tags.iter().cloned()));
+        set(
+            "remaining",
+            self.remaining.map(|r| r.as_secs_f64() * 1000.0).into(),
+        );
+        object.into()
+    }
+
+    pub fn from_js(value: &JsValue) -> Option<Self> {
+        let get = |key: &str| Reflect::get(value, &JsValue::from_str(key)).ok();
+        let string = |key: &str| get(key).and_then(|v| v.as_string());
+        let bool = |key: &str| get(key).and_then(|v| v.as_bool());
+        let strings = |key: &str| {
+            get(key).filter(Array::is_array).map(|v| {
+                Array::from(&v)
+                    .iter()
+                    .filter_map(|v| v.as_string())
+                    .collect()
+            })
+        };
+        Some(Self {
+            id: ToastId::decode_string(&string("id")?),
+            spec: ToastSpec {
+                title: string("title")?,
+                description: string("description"),
+                close_button: bool("closeButton").unwrap_or(true),
+                invert: bool("invert").unwrap_or_default(),
+                rich_colors: bool("richColors").unwrap_or_default(),
+            },
+            variant: string("variant")
+                .and_then(|variant| ToastVariant::from_str(&variant).ok())
+                .unwrap_or(ToastVariant::Normal),
+            position: string("position").and_then(|name| {
+                ToasterPosition::ALL
+                    .into_iter()
+                    .find(|position| position_name(*position) == name)
+            }),
+            toaster_id: string("toasterId"),
+            dismissible: bool("dismissible").unwrap_or(true),
+            politeness: string("politeness").and_then(|p| Politeness::from_str(&p).ok()),
+            swipe_directions: strings("swipeDirections").map(|directions: Vec<String>| {
+                directions
+                    .iter()
+                    .filter_map(|d| SwipeDirection::from_str(d).ok())
+                    .collect()
+            }),
+            priority: string("priority")
+                .and_then(|p| Priority::from_str(&p).ok())
+                .unwrap_or_default(),
+            dedupe_key: string("dedupeKey"),
+            throttle_key: string("throttleKey"),
+            tags: strings("tags").unwrap_or_default(),
+            remaining: get("remaining")
+                .and_then(|v| v.as_f64())
+                .map(|ms| Duration::from_millis(ms.max(0.0) as u64)),
+        })
+    }
+}
+
+fn strings(strings: impl Iterator<Item = String>) -> JsValue {
+    strings.map(JsValue::from).collect::<Array>().into()
+}
+
+fn position_name(position: ToasterPosition) -> String {
+    format!("{}-{}", position.y(), position.x())
+}
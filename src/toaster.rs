@@ -1,135 +1,207 @@
 use crate::{
+    hotkey::Hotkey,
     mount_style::mount_style,
-    toast_container::ToastContainer,
-    types::{HeightT, Toasts},
+    swipe::SwipeThresholds,
+    toast_event::ToastEvent,
+    toaster_stack::ToasterStack,
+    types::{
+        Announcements, DismissReason, EvictionPolicy, Politeness, SwipeDirection, Theme,
+        ToastOrder, ToasterMode, ToasterOffset, Toasts,
+    },
     ToastId, ToasterPosition,
 };
-use leptos::prelude::*;
+use leptos::{ev, html, prelude::*};
 use std::time::Duration;
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlElement, PointerEvent};
+use web_sys::HtmlElement;
 
 /// Toaster context provider.
 /// Wrap your app in the Toaster to use the Toasts context in children
 #[component]
 pub fn Toaster(
-    #[prop(default = ToasterPosition::BottomRight)] position: ToasterPosition,
-    #[prop(default = false)] expand: bool,
-    #[prop(default = Duration::from_millis(4000))] duration: Duration,
-    #[prop(default = 14)] gap: usize,
+    /// Set this to have more than one Toaster in the app. Toasts are shown in it by setting the same id in their
+    /// options, while toasts without an id go to the Toaster without one
+    #[prop(optional, into)]
+    toaster_id: Option<String>,
+    #[prop(into, default = ToasterPosition::BottomRight.into())] position: Signal<ToasterPosition>,
+    #[prop(into, default = false.into())] expand: Signal<bool>,
+    #[prop(into, default = Duration::from_millis(4000).into())] duration: Signal<Duration>,
+    #[prop(into, default = 14.into())] gap: Signal<usize>,
+    /// Whether to stack the toasts, or show them one at a time
+    #[prop(into, default = ToasterMode::default().into())]
+    mode: Signal<ToasterMode>,
+    /// Whether new toasts are put in front of or behind the ones already shown
+    #[prop(into, default = ToastOrder::default().into())]
+    order: Signal<ToastOrder>,
     /// The maximum amount of toasts that should be visible at any point
-    #[prop(default = 3)]
-    visible_toasts: usize,
+    #[prop(into, default = 3.into())]
+    visible_toasts: Signal<usize>,
+    /// The width of the toasts, as a CSS length
+    #[prop(into, default = "356px".to_string().into())]
+    width: Signal<String>,
+    /// The distance between the toasts and the edges of the viewport
+    #[prop(into, default = ToasterOffset::from(32).into())]
+    offset: Signal<ToasterOffset>,
+    /// The distance between the toasts and the edges of the viewport on screens narrower than 600px
+    #[prop(into, default = ToasterOffset::symmetric("20px", "16px").into())]
+    mobile_offset: Signal<ToasterOffset>,
+    /// The theme of the built in toasts, unless they set one themselves
+    #[prop(into, default = Theme::Light.into())]
+    theme: Signal<Theme>,
+    /// The maximum amount of toasts in this Toaster at once, see [`Toasts::set_max_toasts`]
+    #[prop(optional, into)]
+    max_toasts: Option<usize>,
+    /// What happens to toasts beyond `max_toasts`
+    #[prop(optional)]
+    eviction_policy: EvictionPolicy,
+    /// Label of the region the toasts are announced in by screen readers
+    #[prop(into, default = "Notifications".to_string())]
+    aria_label: String,
+    /// Keyboard shortcut that focuses and expands the toasts. Set to `None` to disable it.
+    /// When using more than one Toaster, give each its own hotkey
+    #[prop(into, default = Some(Hotkey::default()))]
+    hotkey: Option<Hotkey>,
+    /// The directions toasts can be swiped away in.
+    /// Defaults to the edges of the screen the toasts are positioned at
+    #[prop(optional, into)]
+    swipe_directions: Option<Vec<SwipeDirection>>,
+    /// How far, in pixels, a toast has to be swiped to be dismissed
+    #[prop(default = 20.0)]
+    swipe_threshold: f64,
+    /// How fast, in pixels per millisecond, a toast has to be swiped to be dismissed, even if it was not swiped far enough
+    #[prop(default = 0.11)]
+    swipe_velocity_threshold: f64,
+    /// Whether toasts can be swiped away with a mouse. When disabled only touch and pen input can swipe, so text in the
+    /// toasts can be selected with the mouse
+    #[prop(default = true)]
+    mouse_swipe: bool,
     #[prop(optional)] children: Option<Children>,
 ) -> impl IntoView {
     mount_style("toaster", include_str!("./style.css"));
-    let (expanded, set_expanded) = signal(false);
-    let interacting = RwSignal::new(false);
-    let heights = RwSignal::<Vec<HeightT>>::new(Vec::new());
     let context = use_context::<Toasts>().unwrap_or_else(provide_toasts);
-    let (toasts, set_toasts) = (context.toasts, context.set_toasts);
+    let toasts = context.toasts;
+    if max_toasts.is_some() {
+        context.set_max_toasts(toaster_id.as_deref(), max_toasts, eviction_policy);
+    }
+    let swipe_thresholds = SwipeThresholds {
+        distance: swipe_threshold,
+        velocity: swipe_velocity_threshold,
+    };
+    let section_ref = NodeRef::<html::Section>::new();
+    #[cfg(feature = "builtin_toast")]
+    provide_context(crate::types::ToasterTheme(theme));
+    let stored_toaster_id = StoredValue::new(toaster_id.clone());
+    #[cfg(feature = "builtin_toast")]
+    crate::persist::persist_toasts(context, toaster_id.clone());
 
-    Effect::new(move |_| {
-        // Ensure expanded is always false when no toasts are present / only one left
-        if toasts.with(|t| t.len() <= 1) {
-            set_expanded.set(false);
-        }
+    // Every position that has toasts gets its own stack
+    let positions = Memo::new(move |_| {
+        toasts.with(|toasts| {
+            ToasterPosition::ALL
+                .into_iter()
+                .filter(|p| {
+                    stored_toaster_id.with_value(|toaster_id| {
+                        toasts.iter().any(|t| {
+                            t.options.is_for_toaster(toaster_id.as_deref())
+                                && t.options.position.unwrap_or_else(|| position.get()) == *p
+                        })
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
     });
 
-    let remove_toast = Callback::new(move |toast_id: ToastId| {
-        set_toasts.update(|toasts| {
-            if let Some(index) = toasts.iter().position(|t| t.id == toast_id) {
-                toasts.remove(index);
-            }
-        });
+    let remove_toast = Callback::new(move |(toast_id, reason): (ToastId, DismissReason)| {
+        context.remove(&toast_id, reason);
     });
 
-    let on_pointerdown = move |e: PointerEvent| {
-        let mut is_dismissible = true;
-        if let Some(target) = e.target() {
-            if let Some(element) = target.dyn_ref::<HtmlElement>() {
-                if let Some(dismissible) = element.dataset().get("dismissible") {
-                    is_dismissible = dismissible != "false";
-                }
-            };
-        };
-        if is_dismissible {
-            interacting.set(true);
+    let on_event = Callback::new(move |event: ToastEvent| context.emit(event));
+    let announcements = Announcements::default();
+    let live_region = move |politeness: Politeness| {
+        view! {
+            <div
+                class="leptos-toaster-live-region"
+                role=politeness.role()
+                aria-live=politeness.aria_live()
+                aria-relevant="additions"
+            >
+                <For
+                    each=move || announcements.region(politeness).get()
+                    key=|(toast_id, _)| *toast_id
+                    children=|(_, text)| view! { <p>{text}</p> }
+                />
+            </div>
         }
     };
 
+    let aria_label = match &hotkey {
+        Some(hotkey) => format!("{aria_label} {hotkey}"),
+        None => aria_label,
+    };
+
+    if let Some(hotkey) = hotkey {
+        let handle = window_event_listener(ev::keydown, move |ev| {
+            if !hotkey.matches(&ev) {
+                return;
+            }
+            // Focusing a stack expands it
+            let first_stack = section_ref
+                .get_untracked()
+                .and_then(|section| section.query_selector(".leptos-toaster").ok().flatten())
+                .and_then(|list| list.dyn_into::<HtmlElement>().ok());
+            if let Some(list) = first_stack {
+                ev.prevent_default();
+                let _ = list.focus();
+            }
+        });
+        on_cleanup(move || handle.remove());
+    }
+
     view! {
         {children.map(|v| v())}
 
-        <Show when=move || !toasts.with(|t| t.is_empty())>
-            <section aria-label="Notifications" tab-index=-1>
-                <ol
-                    class="leptos-toaster"
-                    tab-index=-1
-                    data-y-position=position.y()
-                    data-x-position=position.x()
-                    style=("--gap", format!("{}px", gap))
-                    style=("--width", "356px")
-                    style=("--offset", "32px")
-                    style=(
-                        "--front-toast-height",
-                        move || {
-                            format!(
-                                "{}px",
-                                heights
-                                    .with(|heights| {
-                                        heights.first().map(|h| h.height).unwrap_or(0.0)
-                                    }),
-                            )
-                        },
-                    )
-                    on:mouseenter=move |_| set_expanded.set(true)
-                    on:mousemove=move |_| set_expanded.set(true)
-                    on:mouseleave=move |_| {
-                        if !interacting.get() {
-                            set_expanded.set(false)
-                        }
+        <section
+            node_ref=section_ref
+            data-toaster-id=toaster_id
+            data-theme=move || theme.get().to_string()
+            aria-label=aria_label
+            tabindex=-1
+        >
+            // The live regions are always mounted, since screen readers only announce changes to regions that already
+            // exist. Toasts are read out in the one matching their politeness
+            {live_region(Politeness::Polite)}
+            {live_region(Politeness::Assertive)}
+            <For
+                each=move || positions.get()
+                key=move |position| *position
+                children=move |stack_position| {
+                    view! {
+                        <ToasterStack
+                            position=stack_position
+                            default_position=position
+                            toaster_id=stored_toaster_id
+                            toasts
+                            remove_toast
+                            on_event
+                            mounted_toasts=context.mounted
+                            announcements
+                            expand
+                            duration
+                            gap
+                            order
+                            mode
+                            width
+                            offset
+                            mobile_offset
+                            visible_toasts
+                            swipe_directions=swipe_directions.clone()
+                            swipe_thresholds
+                            mouse_swipe
+                        />
                     }
-                    on:pointerdown=on_pointerdown
-                    on:pointerup=move |_| interacting.set(true)
-                >
-                    <For
-                        each=move || toasts.get()
-                        key=move |toast| toast.id
-                        children=move |toast| {
-                            let index = Memo::new(move |_| {
-                                toasts
-                                    .with(|toasts| {
-                                        toasts
-                                            .iter()
-                                            .position(|t| t.id == toast.id)
-                                            .unwrap_or_default()
-                                    })
-                            });
-                            view! {
-                                // Doing this since we
-                                // 1. don't want the view to rerender, and in turn, the ToastContainer to rerender when a new toast is added, because that makes the internal logic more complex. For instance the timeout to delete the toast after the duration would have to keep track of the timeout handle between rerenders. And
-                                // 2. enumerating the toasts vec will not give a reactive index, so we need to get it like this
-                                <ToastContainer
-                                    index=Signal::derive(move || index.get())
-                                    toast
-                                    visible_toasts
-                                    position
-                                    duration_from_toaster=duration
-                                    remove_toast=remove_toast
-                                    expanded
-                                    expand_by_default=expand
-                                    num_toasts=Signal::derive(move || toasts.with(|t| t.len()))
-                                    heights
-                                    gap
-                                />
-                            }
-                        }
-                    />
-
-                </ol>
-            </section>
-        </Show>
+                }
+            />
+        </section>
     }
 }
 
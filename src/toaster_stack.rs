@@ -0,0 +1,409 @@
+use crate::{
+    swipe::SwipeThresholds,
+    toast_container::ToastContainer,
+    toast_event::ToastEvent,
+    types::{
+        Announcements, DismissReason, HeightT, Priority, SwipeDirection, Toast, ToastOrder,
+        ToasterMode, ToasterOffset,
+    },
+    ToastId, ToasterPosition,
+};
+use leptos::{html, prelude::*};
+use std::{
+    cmp::{min, Reverse},
+    collections::HashMap,
+    time::Duration,
+};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, FocusEvent, HtmlElement, KeyboardEvent, Node, PointerEvent};
+
+/// The toasts shown at a single position on the screen.
+/// Every stack keeps track of its own heights and expansion, so toasts at other positions don't affect its layout
+#[component]
+pub fn ToasterStack(
+    position: ToasterPosition,
+    /// The position of toasts that don't set one themselves
+    default_position: Signal<ToasterPosition>,
+    toaster_id: StoredValue<Option<String>>,
+    toasts: ReadSignal<Vec<Toast>>,
+    #[prop(into)] remove_toast: Callback<(ToastId, DismissReason)>,
+    on_event: Callback<ToastEvent>,
+    mounted_toasts: RwSignal<HashMap<ToastId, usize>>,
+    announcements: Announcements,
+    expand: Signal<bool>,
+    duration: Signal<Duration>,
+    gap: Signal<usize>,
+    order: Signal<ToastOrder>,
+    mode: Signal<ToasterMode>,
+    width: Signal<String>,
+    offset: Signal<ToasterOffset>,
+    mobile_offset: Signal<ToasterOffset>,
+    visible_toasts: Signal<usize>,
+    swipe_directions: Option<Vec<SwipeDirection>>,
+    swipe_thresholds: SwipeThresholds,
+    mouse_swipe: bool,
+) -> impl IntoView {
+    let (expanded, set_expanded) = signal(false);
+    let interacting = RwSignal::new(false);
+    // Whether the keyboard focus is somewhere inside the list of toasts. Clicking a toast focuses it as well, but only
+    // keyboard focus expands the stack, as the mouse leaving collapses it again
+    let focused = RwSignal::new(false);
+    let heights = RwSignal::<Vec<HeightT>>::new(Vec::new());
+    let list_ref = NodeRef::<html::Ol>::new();
+
+    let in_stack = move |toast: &Toast| {
+        toaster_id.with_value(|toaster_id| toast.options.is_for_toaster(toaster_id.as_deref()))
+            && toast
+                .options
+                .position
+                .unwrap_or_else(|| default_position.get())
+                == position
+    };
+    // The toast shown in snackbar mode, while the rest of the stack waits without being mounted
+    let snackbar = RwSignal::<Option<ToastId>>::new(None);
+
+    // The toasts of the stack, with the one in front first. Toasts keeps the newest toast first
+    let stacked = move || {
+        let mut stacked = toasts.with(|toasts| {
+            toasts
+                .iter()
+                .filter(|t| in_stack(t))
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        if mode.get() == ToasterMode::Snackbar {
+            let shown = snackbar.get();
+            stacked.retain(|t| Some(t.id) == shown);
+        }
+        if order.get() == ToastOrder::OldestFirst {
+            stacked.reverse();
+        }
+        // Higher priority toasts go in front, keeping the order between toasts of the same priority
+        stacked.sort_by_key(|t| Reverse(t.options.priority));
+        stacked
+    };
+    let ids = Memo::new(move |_| stacked().into_iter().map(|t| t.id).collect::<Vec<_>>());
+
+    Effect::new(move |_| {
+        if mode.get() != ToasterMode::Snackbar {
+            return;
+        }
+        let next = toasts.with(|toasts| {
+            let queue = toasts.iter().filter(|t| in_stack(t)).collect::<Vec<_>>();
+            match snackbar.get_untracked() {
+                Some(shown) if queue.iter().any(|t| t.id == shown) => Some(shown),
+                _ => next_snackbar(&queue),
+            }
+        });
+        if next != snackbar.get_untracked() {
+            snackbar.set(next);
+        }
+    });
+
+    // The shown snackbar, if a toast raised after it should replace it
+    let replaced_snackbar = Memo::new(move |_| {
+        let shown = snackbar.get()?;
+        let is_replaced = mode.get() == ToasterMode::Snackbar
+            && toasts.with(|toasts| {
+                let queue = toasts.iter().filter(|t| in_stack(t)).collect::<Vec<_>>();
+                is_snackbar_replaced(&queue, shown)
+            });
+        is_replaced.then_some(shown)
+    });
+
+    // The heights are kept in the same order as the toasts, which changes with the order and the priorities
+    Effect::new(move |_| {
+        let stack_position = |toast_id: &ToastId| {
+            ids.with(|ids| ids.iter().position(|id| id == toast_id))
+                .unwrap_or(usize::MAX)
+        };
+        let is_sorted = heights.with(|heights| {
+            heights
+                .windows(2)
+                .all(|pair| stack_position(&pair[0].toast_id) <= stack_position(&pair[1].toast_id))
+        });
+        if !is_sorted {
+            heights.update(|heights| heights.sort_by_key(|h| stack_position(&h.toast_id)));
+        }
+    });
+
+    Effect::new(move |_| {
+        // Ensure expanded is always false when no toasts are present / only one left
+        if ids.with(|ids| ids.len() <= 1) {
+            set_expanded.set(false);
+        }
+    });
+
+    Effect::new(move |previous: Option<bool>| {
+        let expanded = expanded.get();
+        if previous.is_some_and(|previous| previous != expanded) {
+            on_event.run(ToastEvent::Expanded {
+                toaster_id: toaster_id.get_value(),
+                position,
+                expanded,
+            });
+        }
+        expanded
+    });
+
+    let on_pointerdown = move |e: PointerEvent| {
+        let mut is_dismissible = true;
+        if let Some(target) = e.target() {
+            if let Some(element) = target.dyn_ref::<HtmlElement>() {
+                if let Some(dismissible) = element.dataset().get("dismissible") {
+                    is_dismissible = dismissible != "false";
+                }
+            };
+        };
+        if is_dismissible {
+            interacting.set(true);
+        }
+    };
+
+    let on_focusin = move |ev: FocusEvent| {
+        let focus_visible = ev
+            .target()
+            .and_then(|target| target.dyn_into::<Element>().ok())
+            .is_some_and(|element| element.matches(":focus-visible").unwrap_or_default());
+        focused.set(focus_visible);
+        if focus_visible {
+            set_expanded.set(true);
+        }
+    };
+
+    let on_focusout = move |ev: FocusEvent| {
+        // Moving the focus between toasts also fires focusout, so only collapse when it leaves the list
+        let focus_stays_inside = ev
+            .related_target()
+            .and_then(|target| target.dyn_into::<Node>().ok())
+            .zip(list_ref.get_untracked())
+            .is_some_and(|(target, list)| list.contains(Some(&target)));
+        if focus_stays_inside {
+            return;
+        }
+        focused.set(false);
+        if !interacting.get_untracked() {
+            set_expanded.set(false);
+        }
+    };
+
+    let on_keydown = move |ev: KeyboardEvent| {
+        // The front toast is the one closest to the edge of the screen
+        let towards_front = match (ev.key().as_str(), position.y().as_str()) {
+            ("ArrowUp", "top") | ("ArrowDown", "bottom") => true,
+            ("ArrowUp", "bottom") | ("ArrowDown", "top") => false,
+            _ => return,
+        };
+        if let Some(list) = list_ref.get_untracked() {
+            ev.prevent_default();
+            focus_sibling_toast(&list, towards_front);
+        }
+    };
+
+    view! {
+        <ol
+            node_ref=list_ref
+            class="leptos-toaster"
+            tabindex=-1
+            data-y-position=position.y()
+            data-x-position=position.x()
+            style=("--gap", move || format!("{}px", gap.get()))
+            style=("--width", move || width.get())
+            style=("--offset-top", move || offset.with(|o| o.top.clone()))
+            style=("--offset-right", move || offset.with(|o| o.right.clone()))
+            style=("--offset-bottom", move || offset.with(|o| o.bottom.clone()))
+            style=("--offset-left", move || offset.with(|o| o.left.clone()))
+            style=("--mobile-offset-top", move || mobile_offset.with(|o| o.top.clone()))
+            style=("--mobile-offset-right", move || mobile_offset.with(|o| o.right.clone()))
+            style=("--mobile-offset-bottom", move || mobile_offset.with(|o| o.bottom.clone()))
+            style=("--mobile-offset-left", move || mobile_offset.with(|o| o.left.clone()))
+            style=(
+                "--front-toast-height",
+                move || {
+                    format!(
+                        "{}px",
+                        heights
+                            .with(|heights| {
+                                heights.first().map(|h| h.height).unwrap_or(0.0)
+                            }),
+                    )
+                },
+            )
+            on:mouseenter=move |_| set_expanded.set(true)
+            on:mousemove=move |_| set_expanded.set(true)
+            on:mouseleave=move |_| {
+                if !interacting.get() && !focused.get() {
+                    set_expanded.set(false)
+                }
+            }
+            on:focusin=on_focusin
+            on:focusout=on_focusout
+            on:keydown=on_keydown
+            on:pointerdown=on_pointerdown
+            on:pointerup=move |_| interacting.set(false)
+            on:pointercancel=move |_| interacting.set(false)
+        >
+            <For
+                each=stacked
+                key=move |toast| toast.id
+                children=move |toast| {
+                    let toast_id = toast.id;
+                    let index = Memo::new(move |_| {
+                        ids.with(|ids| {
+                            ids.iter().position(|id| *id == toast_id).unwrap_or_default()
+                        })
+                    });
+                    view! {
+                        // Doing this since we
+                        // 1. don't want the view to rerender, and in turn, the ToastContainer to rerender when a new toast is added, because that makes the internal logic more complex. For instance the timeout to delete the toast after the duration would have to keep track of the timeout handle between rerenders. And
+                        // 2. enumerating the toasts vec will not give a reactive index, so we need to get it like this
+                        <ToastContainer
+                            index=Signal::derive(move || index.get())
+                            toast
+                            visible_toasts
+                            position
+                            swipe_directions_from_toaster=swipe_directions.clone()
+                            swipe_thresholds
+                            mouse_swipe
+                            duration_from_toaster=duration
+                            remove_toast=remove_toast
+                            on_event
+                            mounted_toasts
+                            announcements
+                            expanded
+                            paused=Signal::derive(move || expanded.get() || focused.get())
+                            expand_by_default=expand
+                            num_toasts=Signal::derive(move || ids.with(|ids| ids.len()))
+                            heights
+                            gap
+                            order
+                            replaced=Signal::derive(move || replaced_snackbar.get() == Some(toast_id))
+                        />
+                    }
+                }
+            />
+
+        </ol>
+    }
+}
+
+/// Picks the toast a snackbar shows next from the queued toasts, newest first.
+/// Toasts that replace the current one jump the queue, all others are shown by priority and then in the order they were
+/// raised
+fn next_snackbar(queue: &[&Toast]) -> Option<ToastId> {
+    queue
+        .iter()
+        .find(|t| t.options.replace_current)
+        .or_else(|| {
+            queue
+                .iter()
+                .rev()
+                .min_by_key(|t| Reverse(t.options.priority))
+        })
+        .map(|t| t.id)
+}
+
+/// Whether a toast raised after the shown snackbar replaces it, from the queued toasts, newest first.
+/// Critical toasts are only replaced by other critical toasts
+fn is_snackbar_replaced(queue: &[&Toast], shown: ToastId) -> bool {
+    let is_critical = |toast: &Toast| toast.options.priority == Priority::Critical;
+    let shown_is_critical = queue.iter().any(|t| t.id == shown && is_critical(t));
+    queue
+        .iter()
+        .take_while(|t| t.id != shown)
+        .any(|t| t.options.replace_current && (!shown_is_critical || is_critical(t)))
+}
+
+/// Moves the focus one toast towards or away from the front, focusing the front toast if none has focus
+fn focus_sibling_toast(list: &HtmlElement, towards_front: bool) {
+    let Ok(nodes) = list.query_selector_all(".leptos-toast-container[data-removed='false']") else {
+        return;
+    };
+    let items = (0..nodes.length())
+        .filter_map(|i| nodes.item(i))
+        .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+        .collect::<Vec<_>>();
+    if items.is_empty() {
+        return;
+    }
+
+    let active = document().active_element();
+    let focused = items
+        .iter()
+        .position(|item| item.contains(active.as_ref().map(|a| a.as_ref())));
+    let next = match focused {
+        Some(index) if towards_front => index.saturating_sub(1),
+        Some(index) => min(index + 1, items.len() - 1),
+        None => 0,
+    };
+    let _ = items[next].focus();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ToastOptions, Toasts};
+
+    fn toast(priority: Priority, replace_current: bool) -> Toast {
+        let options = ToastOptions {
+            priority,
+            replace_current,
+            ..ToastOptions::default()
+        };
+        Toasts::new().new_toast(ViewFn::from(|| "Saved"), None, options)
+    }
+
+    #[test]
+    fn oldest_toast_of_the_highest_priority_goes_next() {
+        // Newest first, like the toasts are kept
+        let queue = [
+            toast(Priority::Normal, false),
+            toast(Priority::High, false),
+            toast(Priority::Normal, false),
+            toast(Priority::High, false),
+        ];
+        let queue = queue.iter().collect::<Vec<_>>();
+        assert_eq!(next_snackbar(&queue), Some(queue[3].id));
+        assert_eq!(next_snackbar(&queue[..3]), Some(queue[1].id));
+        assert_eq!(next_snackbar(&queue[..1]), Some(queue[0].id));
+        assert_eq!(next_snackbar(&[]), None);
+    }
+
+    #[test]
+    fn replace_current_jumps_the_queue() {
+        let queue = [toast(Priority::Low, true), toast(Priority::Critical, false)];
+        let queue = queue.iter().collect::<Vec<_>>();
+        assert_eq!(next_snackbar(&queue), Some(queue[0].id));
+    }
+
+    #[test]
+    fn newer_toasts_replace_the_shown_snackbar() {
+        let queue = [
+            toast(Priority::Normal, false),
+            toast(Priority::Normal, true),
+            toast(Priority::Normal, false),
+        ];
+        let queue = queue.iter().collect::<Vec<_>>();
+        assert!(is_snackbar_replaced(&queue, queue[2].id));
+        // Only toasts raised after the shown one replace it
+        assert!(!is_snackbar_replaced(&queue, queue[1].id));
+        assert!(!is_snackbar_replaced(&queue[..1], queue[0].id));
+    }
+
+    #[test]
+    fn critical_snackbars_are_only_replaced_by_critical_toasts() {
+        let queue = [
+            toast(Priority::High, true),
+            toast(Priority::Critical, false),
+        ];
+        let queue = queue.iter().collect::<Vec<_>>();
+        assert!(!is_snackbar_replaced(&queue, queue[1].id));
+
+        let queue = [
+            toast(Priority::Critical, true),
+            toast(Priority::Critical, false),
+        ];
+        let queue = queue.iter().collect::<Vec<_>>();
+        assert!(is_snackbar_replaced(&queue, queue[1].id));
+    }
+}
@@ -1,16 +1,57 @@
 use leptos::prelude::*;
-use std::time::Duration;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
 use wasm_bindgen::JsValue;
 
-use crate::ToastId;
+use crate::{
+    mute::MuteSettings,
+    throttle::{system_clock, Clock, ThrottleDecision, ThrottledToasts},
+    toast_event::{Listeners, ToastEvent, ToastEventListener},
+    toast_handle::ClosedToasts,
+    Throttle, ToastBuilder, ToastHandle, ToastId,
+};
+#[cfg(feature = "builtin_toast")]
+use crate::{tab_sync::TabSync, ToastSpec};
 
+/// Options for a single toast.
+/// New options might be added in the future, so create these with [`Toasts::build`] or [`ToastOptions::default`]
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct ToastOptions {
     pub dismissible: bool,
     /// Duration until the toast should be dismissed
     pub duration: Option<Duration>,
     /// The position of the toast
     pub position: Option<ToasterPosition>,
+    /// Called with the reason once the toast has been dismissed
+    pub on_dismiss: Option<Callback<DismissReason>>,
+    /// The kind of toast
+    pub variant: ToastVariant,
+    /// How urgently screen readers should announce the toast.
+    /// Defaults to [`Politeness::Assertive`] for error toasts and [`Politeness::Polite`] for all others
+    pub politeness: Option<Politeness>,
+    /// The directions the toast can be swiped away in, overriding the ones set on the Toaster
+    pub swipe_directions: Option<Vec<SwipeDirection>>,
+    /// The id of the Toaster that should show the toast. Defaults to the Toaster without an id
+    pub toaster_id: Option<String>,
+    /// In a Toaster in snackbar mode, dismiss the shown toast and show this one right away instead of queueing it
+    pub replace_current: bool,
+    /// How important the toast is compared to the others, which decides which toasts are in front and which are evicted first
+    pub priority: Priority,
+    /// Raising a toast with the same key as a shown or queued one bumps the count on that toast and restarts its timer,
+    /// instead of showing another one
+    pub dedupe_key: Option<String>,
+    /// Toasts with the same key are limited by the throttle set with [`Toasts::set_throttle`]
+    pub throttle_key: Option<String>,
+    /// Groups the toast belongs to, see [`Toasts::dismiss_group`]
+    pub tags: Vec<String>,
+    /// Keep the toast across page loads in the same tab, with the time it has left.
+    /// Only toasts created from a `ToastSpec` with the `builtin_toast` feature can be persisted
+    pub persist: bool,
 }
 
 impl Default for ToastOptions {
@@ -19,6 +60,203 @@ impl Default for ToastOptions {
             dismissible: true,
             duration: None,
             position: None,
+            on_dismiss: None,
+            variant: ToastVariant::Normal,
+            politeness: None,
+            swipe_directions: None,
+            toaster_id: None,
+            replace_current: false,
+            priority: Priority::Normal,
+            dedupe_key: None,
+            throttle_key: None,
+            tags: Vec::new(),
+            persist: false,
+        }
+    }
+}
+
+impl ToastOptions {
+    pub(crate) fn is_for_toaster(&self, toaster_id: Option<&str>) -> bool {
+        self.toaster_id.as_deref() == toaster_id
+    }
+
+    pub(crate) fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    pub(crate) fn effective_politeness(&self) -> Politeness {
+        self.politeness.unwrap_or(match self.variant {
+            ToastVariant::Error => Politeness::Assertive,
+            _ => Politeness::Polite,
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub enum ToastVariant {
+    Normal,
+    Success,
+    Info,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for ToastVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ToastVariant::Normal => write!(f, "normal"),
+            ToastVariant::Success => write!(f, "success"),
+            ToastVariant::Info => write!(f, "info"),
+            ToastVariant::Warning => write!(f, "warning"),
+            ToastVariant::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl std::str::FromStr for ToastVariant {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(ToastVariant::Normal),
+            "success" => Ok(ToastVariant::Success),
+            "info" => Ok(ToastVariant::Info),
+            "warning" => Ok(ToastVariant::Warning),
+            "error" => Ok(ToastVariant::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl std::fmt::Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Theme::Light => write!(f, "light"),
+            Theme::Dark => write!(f, "dark"),
+        }
+    }
+}
+
+/// The theme of the closest Toaster, provided as context for the built in toasts
+#[cfg(feature = "builtin_toast")]
+#[derive(Clone, Copy)]
+pub(crate) struct ToasterTheme(pub Signal<Theme>);
+
+/// How urgently screen readers should announce a toast
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Politeness {
+    /// Announced once the screen reader is done with what it is currently reading
+    Polite,
+    /// Announced right away, interrupting the screen reader
+    Assertive,
+}
+
+impl Politeness {
+    pub fn role(&self) -> String {
+        match self {
+            Politeness::Polite => "status".to_string(),
+            Politeness::Assertive => "alert".to_string(),
+        }
+    }
+
+    pub fn aria_live(&self) -> String {
+        match self {
+            Politeness::Polite => "polite".to_string(),
+            Politeness::Assertive => "assertive".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Politeness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.aria_live())
+    }
+}
+
+impl std::str::FromStr for Politeness {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "polite" => Ok(Politeness::Polite),
+            "assertive" => Ok(Politeness::Assertive),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How important a toast is. Toasts with a higher priority are stacked in front of the others, regardless of the
+/// [`ToastOrder`], and toasts with a lower priority are evicted first
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    /// Always visible, and never evicted or queued
+    Critical,
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Priority::Low => write!(f, "low"),
+            Priority::Normal => write!(f, "normal"),
+            Priority::High => write!(f, "high"),
+            Priority::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl std::str::FromStr for Priority {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Priority::Low),
+            "normal" => Ok(Priority::Normal),
+            "high" => Ok(Priority::High),
+            "critical" => Ok(Priority::Critical),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Why a toast was dismissed
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DismissReason {
+    /// The duration of the toast ran out
+    Timeout,
+    /// The user swiped the toast away
+    Swipe,
+    /// Another toast took its place, in a Toaster in snackbar mode
+    Replaced,
+    /// There were too many toasts, see [`Toasts::set_max_toasts`]
+    Evicted,
+    /// There were too many toasts with the same throttle key, see [`Toasts::set_throttle`]
+    Throttled,
+    /// The toast was muted, see [`Toasts::set_mute_settings`]
+    Muted,
+    /// The toast was dismissed through the close button, [`dismiss_toast`], [`Toasts::dismiss`] or a [`ToastHandle`]
+    Dismissed,
+}
+
+impl std::fmt::Display for DismissReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DismissReason::Timeout => write!(f, "timeout"),
+            DismissReason::Swipe => write!(f, "swipe"),
+            DismissReason::Replaced => write!(f, "replaced"),
+            DismissReason::Evicted => write!(f, "evicted"),
+            DismissReason::Throttled => write!(f, "throttled"),
+            DismissReason::Muted => write!(f, "muted"),
+            DismissReason::Dismissed => write!(f, "dismissed"),
         }
     }
 }
@@ -26,47 +264,780 @@ impl Default for ToastOptions {
 #[derive(Clone)]
 pub struct Toast {
     pub id: ToastId,
-    pub view: ViewFn,
+    /// Kept in a signal so the view can be swapped with [`ToastHandle::update`] without remounting the toast
+    pub view: ArcRwSignal<ViewFn>,
     pub options: ToastOptions,
+    /// How often the toast has been raised, counting the repeats with the same dedupe key
+    pub count: ArcRwSignal<usize>,
+    /// When the toast was raised, by the clock of the Toasts
+    pub created_at: Duration,
+    /// The content of the toast as data, if it was created from a spec
+    #[cfg(feature = "builtin_toast")]
+    pub spec: Option<ToastSpec>,
+    pub(crate) timer: ArcStoredValue<ToastTimer>,
+    /// Whether the toast has been mounted. It is mounted again when it moves to another stack, e.g. because the position
+    /// of the Toaster changed
+    pub(crate) was_shown: ArcStoredValue<bool>,
+    /// Whether the toast is being animated out, after which it is removed
+    pub(crate) closing: ArcStoredValue<bool>,
+}
+
+/// The timer dismissing a toast, kept with the toast so the remaining time can be persisted
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct ToastTimer {
+    /// Known once the toast is shown
+    pub duration: Option<Duration>,
+    /// How long the timer has run so far, not counting the currently running timer
+    pub elapsed: Duration,
+    /// When the currently running timer was started, in milliseconds
+    pub started_at: Option<f64>,
+}
+
+impl ToastTimer {
+    /// Stops the running timer, adding the time it ran to the elapsed time
+    pub fn stop(&mut self, now: f64) {
+        if let Some(started_at) = self.started_at.take() {
+            self.elapsed += Duration::from_millis((now - started_at).max(0.0) as u64);
+        }
+    }
+
+    /// How much time is left before the toast is dismissed, if it has been shown
+    #[cfg(feature = "builtin_toast")]
+    pub fn remaining(&self, now: f64) -> Option<Duration> {
+        let mut timer = *self;
+        timer.stop(now);
+        Some(timer.duration?.saturating_sub(timer.elapsed))
+    }
+}
+
+/// A toast as it was when it was looked at, see [`Toasts::snapshot`]
+#[derive(Clone)]
+pub struct ToastSnapshot {
+    pub id: ToastId,
+    /// The options the toast was raised with, including its variant and tags
+    pub options: ToastOptions,
+    /// How often the toast has been raised, counting the repeats with the same dedupe key
+    pub count: usize,
+    /// When the toast was raised, by the clock of the Toasts
+    pub created_at: Duration,
+}
+
+impl From<&Toast> for ToastSnapshot {
+    fn from(toast: &Toast) -> Self {
+        Self {
+            id: toast.id,
+            options: toast.options.clone(),
+            count: toast.count.get_untracked(),
+            created_at: toast.created_at,
+        }
+    }
+}
+
+/// A toast that is no longer shown, see [`Toasts::history`]
+#[derive(Clone)]
+pub struct HistoryEntry {
+    pub id: ToastId,
+    pub view: ViewFn,
+    pub variant: ToastVariant,
+    pub tags: Vec<String>,
+    /// When the toast was raised, by the clock of the Toasts
+    pub created_at: Duration,
+    /// When the toast was dismissed, by the clock of the Toasts, see [`Toasts::set_clock`]
+    pub dismissed_at: Duration,
+    pub reason: DismissReason,
+    pub read: bool,
 }
 
 #[derive(Clone, Copy)]
 pub struct Toasts {
     pub(crate) toasts: ReadSignal<Vec<Toast>>,
     pub(crate) set_toasts: WriteSignal<Vec<Toast>>,
+    pub(crate) closed: StoredValue<ClosedToasts>,
+    /// The maximum amount of toasts per Toaster, and what to do with toasts beyond that
+    limits: StoredValue<HashMap<Option<String>, (usize, EvictionPolicy)>>,
+    /// Toasts waiting for room with [`EvictionPolicy::Queue`]
+    queued: StoredValue<VecDeque<Toast>>,
+    throttle: StoredValue<Option<Throttle>>,
+    throttled: StoredValue<ThrottledToasts>,
+    clock: StoredValue<Clock>,
+    mute_settings: RwSignal<MuteSettings>,
+    /// The latest muted toasts
+    muted: RwSignal<VecDeque<Toast>>,
+    /// How many toasts were muted since do not disturb was switched on
+    muted_during_do_not_disturb: StoredValue<usize>,
+    do_not_disturb_summary: StoredValue<Option<Summary>>,
+    /// The latest dismissed toasts, newest first
+    history: RwSignal<VecDeque<HistoryEntry>>,
+    history_size: StoredValue<usize>,
+    listeners: StoredValue<Listeners>,
+    /// How many containers every mounted toast has. Moving a toast to another stack briefly mounts it twice
+    pub(crate) mounted: RwSignal<HashMap<ToastId, usize>>,
+    #[cfg(feature = "builtin_toast")]
+    pub(crate) tab_sync: StoredValue<Option<TabSync>, LocalStorage>,
 }
 
+/// Creates the view of a toast summarizing how many toasts were left out
+pub(crate) type Summary = Arc<dyn Fn(usize) -> ViewFn + Send + Sync>;
+
+/// How many muted toasts are kept around
+const MAX_MUTED_TOASTS: usize = 100;
+
+/// How many dismissed toasts are kept in the history, unless set otherwise
+const DEFAULT_HISTORY_SIZE: usize = 50;
+
 impl Toasts {
     /// Create a new toasts
     pub fn new() -> Self {
         let (toasts, set_toasts) = signal(Vec::new());
-        Self { toasts, set_toasts }
+        Self {
+            toasts,
+            set_toasts,
+            closed: StoredValue::new(ClosedToasts::default()),
+            limits: StoredValue::new(HashMap::new()),
+            queued: StoredValue::new(VecDeque::new()),
+            throttle: StoredValue::new(None),
+            throttled: StoredValue::new(ThrottledToasts::default()),
+            clock: StoredValue::new(system_clock()),
+            mute_settings: RwSignal::new(MuteSettings::default()),
+            muted: RwSignal::new(VecDeque::new()),
+            muted_during_do_not_disturb: StoredValue::new(0),
+            do_not_disturb_summary: StoredValue::new(None),
+            history: RwSignal::new(VecDeque::new()),
+            history_size: StoredValue::new(DEFAULT_HISTORY_SIZE),
+            listeners: StoredValue::new(Listeners::default()),
+            mounted: RwSignal::new(HashMap::new()),
+            #[cfg(feature = "builtin_toast")]
+            tab_sync: StoredValue::new_local(None),
+        }
+    }
+
+    /// Call the function with everything that happens to the toasts, until the returned listener is removed
+    pub fn on_event(
+        &self,
+        listener: impl Fn(&ToastEvent) + Send + Sync + 'static,
+    ) -> ToastEventListener {
+        let id = self
+            .listeners
+            .try_update_value(|listeners| listeners.add(Arc::new(listener)))
+            .unwrap_or_default();
+        ToastEventListener { id, toasts: *self }
+    }
+
+    pub(crate) fn remove_listener(&self, id: usize) {
+        self.listeners
+            .update_value(|listeners| listeners.remove(id));
+    }
+
+    pub(crate) fn emit(&self, event: ToastEvent) {
+        // The listeners are called outside of the stored value, so they can add or remove listeners themselves, and
+        // untracked, so the effects emitting events don't rerun when the listeners read signals
+        let listeners = self
+            .listeners
+            .try_with_value(|listeners| listeners.all())
+            .unwrap_or_default();
+        untrack(|| {
+            for listener in listeners {
+                listener(&event);
+            }
+        });
+    }
+
+    /// How many toasts are shown, not counting queued ones
+    pub fn count(&self) -> usize {
+        self.with_shown(|shown| shown.len())
+    }
+
+    /// Whether no toasts are shown
+    pub fn is_empty(&self) -> bool {
+        self.with_shown(|shown| shown.is_empty())
+    }
+
+    /// How many toasts of each variant are shown. Variants without toasts are left out
+    pub fn count_by_variant(&self) -> HashMap<ToastVariant, usize> {
+        self.with_shown(|shown| {
+            let mut counts = HashMap::new();
+            for toast in shown {
+                *counts.entry(toast.options.variant).or_default() += 1;
+            }
+            counts
+        })
+    }
+
+    /// The ids of the shown toasts, newest first
+    pub fn ids(&self) -> Vec<ToastId> {
+        self.with_shown(|shown| shown.iter().map(|t| t.id).collect())
+    }
+
+    /// A copy of the shown toasts, newest first
+    pub fn snapshot(&self) -> Vec<ToastSnapshot> {
+        self.with_shown(|shown| shown.iter().map(|t| ToastSnapshot::from(*t)).collect())
+    }
+
+    /// Runs the function with the toasts mounted in a Toaster, newest first.
+    /// Toasts queued by [`Toasts::set_max_toasts`] or waiting for their turn in a snackbar are left out
+    fn with_shown<T>(&self, f: impl FnOnce(&[&Toast]) -> T) -> T {
+        self.mounted.with(|mounted| {
+            self.toasts.with(|toasts| {
+                let shown = toasts
+                    .iter()
+                    .filter(|t| mounted.contains_key(&t.id))
+                    .collect::<Vec<_>>();
+                f(&shown)
+            })
+        })
+    }
+
+    /// Whether the toast is mounted in a Toaster, and can be animated out
+    pub(crate) fn is_mounted(&self, toast_id: &ToastId) -> bool {
+        self.mounted.with(|mounted| mounted.contains_key(toast_id))
+    }
+
+    /// Limit how many toasts the Toaster with the id can have at once, deciding with the policy what happens to toasts
+    /// beyond that. Only toasts for that Toaster count towards the limit, see [`ToastOptions::toaster_id`].
+    /// Unlike `visible_toasts` on the Toaster, toasts beyond the limit are not mounted at all
+    pub fn set_max_toasts(
+        &self,
+        toaster_id: Option<&str>,
+        max_toasts: Option<usize>,
+        policy: EvictionPolicy,
+    ) {
+        let toaster_id = toaster_id.map(str::to_string);
+        self.limits.update_value(|limits| match max_toasts {
+            Some(max_toasts) => {
+                limits.insert(toaster_id, (max_toasts, policy));
+            }
+            None => {
+                limits.remove(&toaster_id);
+            }
+        });
+        self.show_queued();
+    }
+
+    /// Limit how many toasts with the same throttle key are shown within a time window
+    pub fn set_throttle(&self, throttle: Option<Throttle>) {
+        self.throttle.set_value(throttle);
+    }
+
+    /// Replace the clock the throttle window is measured with, which returns the time since the Unix epoch.
+    /// Defaults to the system time, but can be replaced to control the time in tests.
+    /// The clock also timestamps the toasts and the history, which [`ToastHistory`](crate::ToastHistory) shows as a time
+    /// of day, so a replaced clock should still count from the Unix epoch if the history is shown
+    pub fn set_clock(&self, clock: impl Fn() -> Duration + Send + Sync + 'static) {
+        self.clock.set_value(Arc::new(clock));
+    }
+
+    /// Which toasts are muted
+    pub fn mute_settings(&self) -> MuteSettings {
+        self.mute_settings.get()
+    }
+
+    /// Replace the mute settings. Switching off do not disturb shows the summary of the toasts it muted, if there is one
+    pub fn set_mute_settings(&self, settings: MuteSettings) {
+        let was_do_not_disturb = self.mute_settings.with_untracked(|s| s.do_not_disturb);
+        let do_not_disturb = settings.do_not_disturb;
+        self.mute_settings.set(settings);
+        if do_not_disturb && !was_do_not_disturb {
+            self.muted_during_do_not_disturb.set_value(0);
+        } else if !do_not_disturb && was_do_not_disturb {
+            self.show_do_not_disturb_summary();
+        }
+    }
+
+    /// Mute all toasts, except for critical ones, until it is switched off again
+    pub fn set_do_not_disturb(&self, do_not_disturb: bool) {
+        self.set_mute_settings(MuteSettings {
+            do_not_disturb,
+            ..self.mute_settings.get_untracked()
+        });
+    }
+
+    pub fn mute_tag(&self, tag: impl Into<String>) {
+        self.mute_settings.update(|s| {
+            s.tags.insert(tag.into());
+        });
+    }
+
+    pub fn unmute_tag(&self, tag: &str) {
+        self.mute_settings.update(|s| {
+            s.tags.remove(tag);
+        });
+    }
+
+    pub fn mute_variant(&self, variant: ToastVariant) {
+        self.mute_settings.update(|s| {
+            s.variants.insert(variant);
+        });
+    }
+
+    pub fn unmute_variant(&self, variant: ToastVariant) {
+        self.mute_settings.update(|s| {
+            s.variants.remove(&variant);
+        });
+    }
+
+    /// Once do not disturb is switched off, show a toast with the view created from how many toasts were muted by it
+    pub fn summarize_do_not_disturb<F, V>(&self, summary: F)
+    where
+        F: Fn(usize) -> V + Clone + Send + Sync + 'static,
+        V: IntoView + 'static,
+    {
+        self.do_not_disturb_summary
+            .set_value(Some(Arc::new(move |muted| {
+                let summary = summary.clone();
+                ViewFn::from(move || summary(muted))
+            })));
+    }
+
+    /// Load the mute settings from the local storage of the browser, and save them there whenever they change
+    pub fn persist_mute_settings(&self, storage_key: impl Into<String>) {
+        let toasts = *self;
+        let storage_key = storage_key.into();
+        // Effects only run in the browser, where the local storage is. The settings are loaded before they are first
+        // saved, so the defaults don't overwrite them
+        Effect::new(move |loaded: Option<()>| {
+            if loaded.is_none() {
+                untrack(|| {
+                    if let Some(settings) = MuteSettings::load(&storage_key) {
+                        toasts.set_mute_settings(settings);
+                    }
+                });
+            }
+            toasts
+                .mute_settings
+                .with(|settings| settings.save(&storage_key));
+        });
+    }
+
+    /// The latest toasts that were muted instead of shown, newest first
+    pub fn muted_toasts(&self) -> Vec<ToastSnapshot> {
+        self.muted
+            .with(|muted| muted.iter().map(ToastSnapshot::from).collect())
+    }
+
+    pub fn clear_muted_toasts(&self) {
+        self.muted.update(|muted| muted.clear());
+    }
+
+    /// The latest dismissed toasts, newest first. Muted, throttled and evicted toasts are in it as well
+    pub fn history(&self) -> Vec<HistoryEntry> {
+        self.history
+            .with(|history| history.iter().cloned().collect())
+    }
+
+    /// How many toasts are kept in the history, dropping the oldest ones beyond that
+    pub fn set_history_size(&self, history_size: usize) {
+        self.history_size.set_value(history_size);
+        self.history
+            .update(|history| history.truncate(history_size));
     }
 
-    /// Create a new toast
+    /// How many toasts in the history have not been marked as read
+    pub fn unread_count(&self) -> usize {
+        self.history
+            .with(|history| history.iter().filter(|entry| !entry.read).count())
+    }
+
+    pub fn mark_history_read(&self) {
+        self.history.update(|history| {
+            for entry in history.iter_mut() {
+                entry.read = true;
+            }
+        });
+    }
+
+    pub fn clear_history(&self) {
+        self.history.update(|history| history.clear());
+    }
+
+    /// Start building a new toast, which is shown once [`ToastBuilder::show`] is called
+    pub fn build(&self, toast: impl Into<ViewFn>) -> ToastBuilder {
+        ToastBuilder::new(*self, toast.into())
+    }
+
+    /// Start building a new toast rendered by the built in Toast from the spec.
+    /// Unlike other toasts, these can be persisted with [`ToastOptions::persist`]
+    #[cfg(feature = "builtin_toast")]
+    pub fn build_spec(&self, spec: ToastSpec) -> ToastBuilder {
+        ToastBuilder::from_spec(*self, spec)
+    }
+
+    /// Show the toasts created from a spec in all tabs that sync through the channel, and dismiss them in all of those
+    /// tabs when they are dismissed in one. Stops syncing once the current owner, e.g. the component calling this, is
+    /// cleaned up
+    #[cfg(feature = "builtin_toast")]
+    pub fn sync_across_tabs(&self, channel_name: impl Into<String>) {
+        let toasts = *self;
+        let channel_name = channel_name.into();
+        // Effects only run in the browser, where the channel is
+        Effect::new(move |_| untrack(|| crate::tab_sync::connect(toasts, &channel_name)));
+    }
+
+    /// Create a new toast, returning a handle to it.
+    /// If there already is a toast with the same dedupe key, that toast is bumped instead and the handle is to it
     pub fn toast(
         &self,
         toast: impl Into<ViewFn>,
         id: Option<ToastId>,
         options: Option<ToastOptions>,
-    ) {
+    ) -> ToastHandle {
+        self.add(self.new_toast(toast.into(), id, options.unwrap_or_default()))
+    }
+
+    /// Create a new toast rendered by the built in Toast from the spec, returning a handle to it
+    #[cfg(feature = "builtin_toast")]
+    pub fn toast_from_spec(
+        &self,
+        spec: ToastSpec,
+        id: Option<ToastId>,
+        options: Option<ToastOptions>,
+    ) -> ToastHandle {
         let id = id.unwrap_or_else(ToastId::new);
-        let toast = Toast {
-            id,
-            view: toast.into(),
-            options: options.unwrap_or_default(),
-        };
-        let mut toasts = self.set_toasts.write();
-        toasts.insert(0, toast);
+        let options = options.unwrap_or_default();
+        let view = spec.view(id, options.variant);
+        let mut toast = self.new_toast(view, Some(id), options);
+        toast.spec = Some(spec);
+        self.add(toast)
     }
 
+    pub(crate) fn new_toast(
+        &self,
+        view: ViewFn,
+        id: Option<ToastId>,
+        options: ToastOptions,
+    ) -> Toast {
+        Toast {
+            id: id.unwrap_or_else(ToastId::new),
+            view: ArcRwSignal::new(view),
+            options,
+            count: ArcRwSignal::new(1),
+            created_at: self.clock.with_value(|clock| clock()),
+            #[cfg(feature = "builtin_toast")]
+            spec: None,
+            timer: ArcStoredValue::new(ToastTimer::default()),
+            was_shown: ArcStoredValue::new(false),
+            closing: ArcStoredValue::new(false),
+        }
+    }
+
+    fn add(&self, toast: Toast) -> ToastHandle {
+        if let Some(dedupe_key) = &toast.options.dedupe_key {
+            if let Some(existing) =
+                self.find_by_dedupe_key(dedupe_key, toast.options.toaster_id.as_deref())
+            {
+                existing.count.update(|count| *count += 1);
+                self.emit(ToastEvent::Updated(existing.id));
+                return ToastHandle::new(existing.id, *self);
+            }
+        }
+
+        let id = toast.id;
+        // The id might be reused, so forget about how an earlier toast with it was closed
+        self.closed.update_value(|closed| closed.forget(&id));
+
+        if let Some(by_do_not_disturb) = self.mute_check(&toast.options) {
+            if by_do_not_disturb {
+                self.muted_during_do_not_disturb
+                    .update_value(|muted| *muted += 1);
+            }
+            self.muted.update(|muted| {
+                muted.truncate(MAX_MUTED_TOASTS - 1);
+                muted.push_front(toast.clone());
+            });
+            self.close(toast, DismissReason::Muted);
+            return ToastHandle::new(id, *self);
+        }
+
+        if let Some(throttled) = self.throttle_check(&toast.options) {
+            let throttle_key = toast.options.throttle_key.clone().unwrap_or_default();
+            self.show_throttle_summary(&throttle_key, throttled, &toast.options);
+            self.close(toast, DismissReason::Throttled);
+            return ToastHandle::new(id, *self);
+        }
+
+        let toaster_id = toast.options.toaster_id.clone();
+        let is_full = !self.has_room(toaster_id.as_deref());
+        let is_critical = toast.options.priority == Priority::Critical;
+        match self.limit(toaster_id.as_deref()) {
+            // Critical toasts are shown right away, even if that means going over the limit
+            Some((_, EvictionPolicy::Queue)) if is_full && !is_critical => {
+                self.queued.update_value(|queued| queued.push_back(toast));
+            }
+            Some((_, policy)) if is_full && policy != EvictionPolicy::Queue => {
+                let victim = self.toasts.with_untracked(|toasts| {
+                    let toasts = toasts
+                        .iter()
+                        .filter(|t| t.options.is_for_toaster(toaster_id.as_deref()));
+                    eviction_victim(&toast, toasts, policy)
+                });
+                match victim {
+                    Some(victim) if victim == id => self.close(toast, DismissReason::Evicted),
+                    Some(victim) => {
+                        self.remove(&victim, DismissReason::Evicted);
+                        self.set_toasts.write().insert(0, toast);
+                    }
+                    None => self.set_toasts.write().insert(0, toast),
+                }
+            }
+            _ => self.set_toasts.write().insert(0, toast),
+        }
+        // Only toasts that made it past the mute settings, the throttle and the limit are shown in the other tabs
+        #[cfg(feature = "builtin_toast")]
+        self.find(&id, |toast| crate::tab_sync::send_shown(*self, toast));
+        ToastHandle::new(id, *self)
+    }
+
+    /// Removes the toast right away, without animating it out
     pub fn dismiss(&self, toast_id: &ToastId) {
+        self.remove(toast_id, DismissReason::Dismissed);
+    }
+
+    /// Dismiss all shown and queued toasts with the tag, animating out the shown ones
+    pub fn dismiss_group(&self, tag: &str) {
+        for toast in self.toasts_in_group(tag) {
+            toast.dismiss();
+        }
+    }
+
+    /// Handles to the shown and queued toasts with the tag, shown toasts first.
+    /// Only changes to the shown toasts are tracked
+    pub fn toasts_in_group(&self, tag: &str) -> Vec<ToastHandle> {
+        let mut ids = self.toasts.with(|toasts| {
+            toasts
+                .iter()
+                .filter(|t| t.options.has_tag(tag))
+                .map(|t| t.id)
+                .collect::<Vec<_>>()
+        });
+        self.queued.with_value(|queued| {
+            ids.extend(
+                queued
+                    .iter()
+                    .filter(|t| t.options.has_tag(tag))
+                    .map(|t| t.id),
+            )
+        });
+        ids.into_iter()
+            .map(|id| ToastHandle::new(id, *self))
+            .collect()
+    }
+
+    /// Removes the toast, shown or queued, and wakes up anyone waiting for it to close
+    pub(crate) fn remove(&self, toast_id: &ToastId, reason: DismissReason) {
+        let mut removed = None;
         self.set_toasts.update(|toasts| {
             if let Some(index) = toasts.iter().position(|t| &t.id == toast_id) {
-                toasts.remove(index);
+                removed = Some(toasts.remove(index));
+            };
+        });
+        if removed.is_none() {
+            self.queued.update_value(|queued| {
+                if let Some(index) = queued.iter().position(|t| &t.id == toast_id) {
+                    removed = queued.remove(index);
+                }
+            });
+        }
+        if let Some(toast) = removed {
+            self.close(toast, reason);
+            self.show_queued();
+        }
+    }
+
+    fn close(&self, toast: Toast, reason: DismissReason) {
+        self.closed
+            .update_value(|closed| closed.close(toast.id, reason));
+        // Only the user dismissing a toast dismisses it in the other tabs, which run their own timers and limits
+        #[cfg(feature = "builtin_toast")]
+        if matches!(reason, DismissReason::Dismissed | DismissReason::Swipe) {
+            crate::tab_sync::send_dismissed(*self, &toast);
+        }
+        let history_size = self.history_size.get_value();
+        if history_size > 0 {
+            let entry = HistoryEntry {
+                id: toast.id,
+                view: toast.view.get_untracked(),
+                variant: toast.options.variant,
+                tags: toast.options.tags.clone(),
+                created_at: toast.created_at,
+                dismissed_at: self.clock.with_value(|clock| clock()),
+                reason,
+                read: false,
             };
+            self.history.update(|history| {
+                history.truncate(history_size - 1);
+                history.push_front(entry);
+            });
+        }
+        if let Some(on_dismiss) = toast.options.on_dismiss {
+            on_dismiss.run(reason);
+        }
+        self.emit(ToastEvent::Dismissed {
+            id: toast.id,
+            reason,
         });
     }
+
+    /// Whether the toast is muted, and if so whether that is only because of do not disturb
+    fn mute_check(&self, options: &ToastOptions) -> Option<bool> {
+        self.mute_settings.with_untracked(|settings| {
+            if settings.variants.contains(&options.variant)
+                || options.tags.iter().any(|tag| settings.tags.contains(tag))
+            {
+                Some(false)
+            } else if settings.do_not_disturb && options.priority != Priority::Critical {
+                Some(true)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn show_do_not_disturb_summary(&self) {
+        let muted = self.muted_during_do_not_disturb.get_value();
+        self.muted_during_do_not_disturb.set_value(0);
+        if muted == 0 {
+            return;
+        }
+        let view = self
+            .do_not_disturb_summary
+            .with_value(|summary| summary.as_ref().map(|summary| summary(muted)));
+        if let Some(view) = view {
+            self.toast(view, None, None);
+        }
+    }
+
+    /// How many toasts have been throttled in the current window, if the toast is one of them
+    fn throttle_check(&self, options: &ToastOptions) -> Option<usize> {
+        let throttle_key = options.throttle_key.as_deref()?;
+        let throttle = self.throttle.get_value()?;
+        let now = self.clock.with_value(|clock| clock());
+        let decision = self
+            .throttled
+            .try_update_value(|throttled| throttled.check(throttle_key, now, &throttle))?;
+        match decision {
+            ThrottleDecision::Show => None,
+            ThrottleDecision::Throttle(throttled) => Some(throttled),
+        }
+    }
+
+    /// Shows or updates the toast summarizing the throttled toasts of the key, if the throttle summarizes them
+    fn show_throttle_summary(&self, throttle_key: &str, throttled: usize, options: &ToastOptions) {
+        let Some(view) = self
+            .throttle
+            .with_value(|throttle| throttle.as_ref()?.summary_view(throttled))
+        else {
+            return;
+        };
+        let summary = self
+            .throttled
+            .with_value(|throttled| throttled.summary(throttle_key))
+            .and_then(|summary| self.find(&summary, |t| (t.id, t.view.clone())));
+        match summary {
+            Some((summary_id, summary)) => {
+                summary.set(view);
+                self.emit(ToastEvent::Updated(summary_id));
+            }
+            None => {
+                let options = ToastOptions {
+                    on_dismiss: None,
+                    replace_current: false,
+                    dedupe_key: None,
+                    throttle_key: None,
+                    ..options.clone()
+                };
+                let handle = self.toast(view, None, Some(options));
+                self.throttled
+                    .update_value(|throttled| throttled.set_summary(throttle_key, handle.id()));
+            }
+        }
+    }
+
+    fn limit(&self, toaster_id: Option<&str>) -> Option<(usize, EvictionPolicy)> {
+        self.limits
+            .with_value(|limits| limits.get(&toaster_id.map(str::to_string)).copied())
+    }
+
+    /// Whether the Toaster with the id has room for another toast
+    fn has_room(&self, toaster_id: Option<&str>) -> bool {
+        self.limit(toaster_id).is_none_or(|(max_toasts, _)| {
+            let count = self.toasts.with_untracked(|toasts| {
+                toasts
+                    .iter()
+                    .filter(|t| t.options.is_for_toaster(toaster_id))
+                    .count()
+            });
+            count < max_toasts
+        })
+    }
+
+    /// Moves queued toasts to the shown ones, highest priority first, as long as their Toaster has room for them
+    fn show_queued(&self) {
+        loop {
+            let next = self
+                .queued
+                .try_update_value(|queued| {
+                    let next = queued
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, t)| self.has_room(t.options.toaster_id.as_deref()))
+                        .min_by_key(|(_, t)| Reverse(t.options.priority))
+                        .map(|(index, _)| index)?;
+                    queued.remove(next)
+                })
+                .flatten();
+            match next {
+                Some(toast) => self.set_toasts.write().insert(0, toast),
+                None => break,
+            }
+        }
+    }
+
+    /// Find the toast for the same Toaster with the dedupe key, leaving out toasts that are already being animated out
+    fn find_by_dedupe_key(&self, dedupe_key: &str, toaster_id: Option<&str>) -> Option<Toast> {
+        let has_key = |t: &&Toast| {
+            t.options.dedupe_key.as_deref() == Some(dedupe_key)
+                && t.options.is_for_toaster(toaster_id)
+                && !t.closing.get_value()
+        };
+        self.toasts
+            .with_untracked(|toasts| toasts.iter().find(has_key).cloned())
+            .or_else(|| {
+                self.queued
+                    .with_value(|queued| queued.iter().find(has_key).cloned())
+            })
+    }
+
+    /// Find a toast, whether it is shown or queued
+    pub(crate) fn find<T>(&self, toast_id: &ToastId, f: impl FnOnce(&Toast) -> T) -> Option<T> {
+        let toast = self
+            .toasts
+            .with_untracked(|toasts| toasts.iter().find(|t| &t.id == toast_id).cloned())
+            .or_else(|| {
+                self.queued
+                    .with_value(|queued| queued.iter().find(|t| &t.id == toast_id).cloned())
+            });
+        toast.as_ref().map(f)
+    }
+}
+
+/// Picks the toast to evict when a new toast is created while its Toaster already has as many as allowed.
+/// The toast with the lowest priority goes first, the oldest or newest of them depending on the policy, and critical
+/// toasts are never evicted
+fn eviction_victim<'a>(
+    new: &'a Toast,
+    toasts: impl DoubleEndedIterator<Item = &'a Toast>,
+    policy: EvictionPolicy,
+) -> Option<ToastId> {
+    // Toasts keeps the newest toast first
+    let newest_first = std::iter::once(new).chain(toasts);
+    let candidates: Vec<&Toast> = match policy {
+        EvictionPolicy::DropNewest => newest_first.collect(),
+        _ => newest_first.rev().collect(),
+    };
+    candidates
+        .into_iter()
+        .filter(|t| t.options.priority != Priority::Critical)
+        .min_by_key(|t| t.options.priority)
+        .map(|t| t.id)
 }
 
 impl Default for Toasts {
@@ -87,6 +1058,15 @@ pub enum ToasterPosition {
 }
 
 impl ToasterPosition {
+    pub(crate) const ALL: [ToasterPosition; 6] = [
+        ToasterPosition::TopLeft,
+        ToasterPosition::TopCenter,
+        ToasterPosition::TopRight,
+        ToasterPosition::BottomRight,
+        ToasterPosition::BottomCenter,
+        ToasterPosition::BottomLeft,
+    ];
+
     pub fn x(&self) -> String {
         match self {
             ToasterPosition::TopLeft | ToasterPosition::BottomLeft => "left".to_string(),
@@ -107,6 +1087,168 @@ impl ToasterPosition {
     }
 }
 
+/// What happens when a toast is created while there already are as many as allowed by [`Toasts::set_max_toasts`]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum EvictionPolicy {
+    /// Dismiss the oldest toast to make room for the new one
+    #[default]
+    DropOldest,
+    /// Dismiss the new toast right away
+    DropNewest,
+    /// Keep the new toast waiting until another one is dismissed
+    Queue,
+}
+
+/// How a Toaster shows its toasts
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum ToasterMode {
+    /// Toasts are stacked on top of each other
+    #[default]
+    Stack,
+    /// Only one toast is shown at a time, like a Material snackbar. Other toasts wait in a queue until it is dismissed,
+    /// and their timers don't start until they are shown
+    Snackbar,
+}
+
+/// Which toast is in front of the stack, closest to the edge of the screen
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum ToastOrder {
+    /// New toasts are put in front, pushing the older ones back
+    #[default]
+    NewestFirst,
+    /// New toasts are put at the back, so toasts stay in place until the ones in front of them are dismissed
+    OldestFirst,
+}
+
+/// The distance between the toasts and the edges of the viewport, as CSS lengths
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ToasterOffset {
+    pub top: String,
+    pub right: String,
+    pub bottom: String,
+    pub left: String,
+}
+
+impl ToasterOffset {
+    /// The same offset from every edge
+    pub fn all(offset: impl Into<String>) -> Self {
+        let offset = offset.into();
+        Self {
+            top: offset.clone(),
+            right: offset.clone(),
+            bottom: offset.clone(),
+            left: offset,
+        }
+    }
+
+    /// One offset from the top and bottom, and another from the left and right
+    pub fn symmetric(vertical: impl Into<String>, horizontal: impl Into<String>) -> Self {
+        let (vertical, horizontal) = (vertical.into(), horizontal.into());
+        Self {
+            top: vertical.clone(),
+            right: horizontal.clone(),
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+
+    pub fn top(mut self, offset: impl Into<String>) -> Self {
+        self.top = offset.into();
+        self
+    }
+
+    pub fn right(mut self, offset: impl Into<String>) -> Self {
+        self.right = offset.into();
+        self
+    }
+
+    pub fn bottom(mut self, offset: impl Into<String>) -> Self {
+        self.bottom = offset.into();
+        self
+    }
+
+    pub fn left(mut self, offset: impl Into<String>) -> Self {
+        self.left = offset.into();
+        self
+    }
+}
+
+/// An offset in pixels from every edge
+impl From<u32> for ToasterOffset {
+    fn from(pixels: u32) -> Self {
+        Self::all(format!("{pixels}px"))
+    }
+}
+
+impl From<&str> for ToasterOffset {
+    fn from(offset: &str) -> Self {
+        Self::all(offset)
+    }
+}
+
+impl From<String> for ToasterOffset {
+    fn from(offset: String) -> Self {
+        Self::all(offset)
+    }
+}
+
+/// Directions a toast can be swiped away in
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl std::fmt::Display for SwipeDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwipeDirection::Up => write!(f, "up"),
+            SwipeDirection::Down => write!(f, "down"),
+            SwipeDirection::Left => write!(f, "left"),
+            SwipeDirection::Right => write!(f, "right"),
+        }
+    }
+}
+
+impl std::str::FromStr for SwipeDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "up" => Ok(SwipeDirection::Up),
+            "down" => Ok(SwipeDirection::Down),
+            "left" => Ok(SwipeDirection::Left),
+            "right" => Ok(SwipeDirection::Right),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ToasterPosition {
+    /// Toasts can by default be swiped towards the edges of the screen they are positioned at
+    pub(crate) fn default_swipe_directions(&self) -> Vec<SwipeDirection> {
+        let y = match self {
+            ToasterPosition::TopLeft | ToasterPosition::TopCenter | ToasterPosition::TopRight => {
+                SwipeDirection::Up
+            }
+            ToasterPosition::BottomRight
+            | ToasterPosition::BottomCenter
+            | ToasterPosition::BottomLeft => SwipeDirection::Down,
+        };
+        match self {
+            ToasterPosition::TopLeft | ToasterPosition::BottomLeft => {
+                vec![y, SwipeDirection::Left]
+            }
+            ToasterPosition::TopRight | ToasterPosition::BottomRight => {
+                vec![y, SwipeDirection::Right]
+            }
+            ToasterPosition::TopCenter | ToasterPosition::BottomCenter => vec![y],
+        }
+    }
+}
+
 /// Call this to dismiss the toast with the given id
 pub fn dismiss_toast(toast_id: &ToastId) {
     let message = format!("LEPTOS_TOASTER:{}", toast_id.to_decodable_string());
@@ -125,3 +1267,263 @@ pub struct HeightT {
     pub toast_id: ToastId,
     pub height: f64,
 }
+
+/// What the live regions of a Toaster read out, a region for each [`Politeness`].
+/// The toasts themselves are not live regions, since screen readers only announce changes to regions that already exist
+#[derive(Clone, Copy)]
+pub(crate) struct Announcements {
+    polite: RwSignal<Vec<(ToastId, String)>>,
+    assertive: RwSignal<Vec<(ToastId, String)>>,
+}
+
+impl Default for Announcements {
+    fn default() -> Self {
+        Self {
+            polite: RwSignal::new(Vec::new()),
+            assertive: RwSignal::new(Vec::new()),
+        }
+    }
+}
+
+impl Announcements {
+    pub fn region(&self, politeness: Politeness) -> RwSignal<Vec<(ToastId, String)>> {
+        match politeness {
+            Politeness::Polite => self.polite,
+            Politeness::Assertive => self.assertive,
+        }
+    }
+
+    pub fn announce(&self, toast_id: ToastId, politeness: Politeness, text: String) {
+        self.region(politeness)
+            .update(|region| region.push((toast_id, text)));
+    }
+
+    /// Removes the toast from the regions once it is unmounted, which isn't announced
+    pub fn withdraw(&self, toast_id: &ToastId) {
+        for region in [self.polite, self.assertive] {
+            region.try_update(|region| region.retain(|(id, _)| id != toast_id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The ids of the toasts that are not queued, newest first
+    fn shown(toasts: Toasts) -> Vec<ToastId> {
+        toasts
+            .toasts
+            .with_untracked(|toasts| toasts.iter().map(|t| t.id).collect())
+    }
+
+    fn show(toasts: Toasts) -> ToastId {
+        toasts.build(|| "Saved").show().id()
+    }
+
+    #[test]
+    fn toasts_work_without_a_browser() {
+        let toasts = Toasts::new();
+        let id = toasts.toast(|| "Saved", None, None).id();
+        assert_eq!(toasts.toasts.with_untracked(|toasts| toasts.len()), 1);
+
+        toasts.dismiss(&id);
+        assert!(toasts.toasts.with_untracked(|toasts| toasts.is_empty()));
+        let history = toasts.history();
+        assert_eq!(history[0].id, id);
+        assert_eq!(history[0].reason, DismissReason::Dismissed);
+        assert!(history[0].dismissed_at >= history[0].created_at);
+    }
+
+    #[test]
+    fn dedupe_key_bumps_toasts_for_the_same_toaster() {
+        let toasts = Toasts::new();
+        let show = |toaster: Option<&str>| {
+            let builder = toasts.build(|| "Reconnecting").dedupe_key("reconnecting");
+            match toaster {
+                Some(toaster) => builder.toaster(toaster).show().id(),
+                None => builder.show().id(),
+            }
+        };
+
+        let first = show(None);
+        assert_eq!(show(None), first);
+        assert_eq!(toasts.find(&first, |t| t.count.get_untracked()), Some(2));
+        assert_ne!(show(Some("sidebar")), first);
+
+        // A toast that is being animated out is not bumped, as it is removed right after
+        toasts.find(&first, |t| t.closing.set_value(true));
+        assert_ne!(show(None), first);
+        assert_eq!(toasts.find(&first, |t| t.count.get_untracked()), Some(2));
+    }
+
+    #[test]
+    fn drop_oldest_evicts_the_oldest_toast() {
+        let toasts = Toasts::new();
+        toasts.set_max_toasts(None, Some(2), EvictionPolicy::DropOldest);
+        let [a, b, c] = [show(toasts), show(toasts), show(toasts)];
+        assert_eq!(shown(toasts), vec![c, b]);
+        assert_eq!(toasts.history()[0].id, a);
+        assert_eq!(toasts.history()[0].reason, DismissReason::Evicted);
+    }
+
+    #[test]
+    fn drop_newest_evicts_the_new_toast() {
+        let toasts = Toasts::new();
+        toasts.set_max_toasts(None, Some(2), EvictionPolicy::DropNewest);
+        let [a, b, c] = [show(toasts), show(toasts), show(toasts)];
+        assert_eq!(shown(toasts), vec![b, a]);
+        assert_eq!(toasts.history()[0].id, c);
+        assert_eq!(toasts.history()[0].reason, DismissReason::Evicted);
+    }
+
+    #[test]
+    fn queue_shows_the_toast_once_there_is_room() {
+        let toasts = Toasts::new();
+        toasts.set_max_toasts(None, Some(2), EvictionPolicy::Queue);
+        let [a, b, c] = [show(toasts), show(toasts), show(toasts)];
+        assert_eq!(shown(toasts), vec![b, a]);
+        assert!(toasts.find(&c, |_| ()).is_some());
+        assert!(toasts.history().is_empty());
+
+        toasts.dismiss(&a);
+        assert_eq!(shown(toasts), vec![c, b]);
+    }
+
+    #[test]
+    fn raising_the_limit_shows_queued_toasts() {
+        let toasts = Toasts::new();
+        toasts.set_max_toasts(None, Some(1), EvictionPolicy::Queue);
+        let [a, b] = [show(toasts), show(toasts)];
+        assert_eq!(shown(toasts), vec![a]);
+
+        toasts.set_max_toasts(None, None, EvictionPolicy::Queue);
+        assert_eq!(shown(toasts), vec![b, a]);
+    }
+
+    #[test]
+    fn limit_is_per_toaster() {
+        let toasts = Toasts::new();
+        toasts.set_max_toasts(None, Some(1), EvictionPolicy::DropOldest);
+        let a = show(toasts);
+        let sidebar = toasts.build(|| "Synced").toaster("sidebar").show().id();
+        assert_eq!(shown(toasts), vec![sidebar, a]);
+
+        // Only the toast for the same Toaster makes room
+        let b = show(toasts);
+        assert_eq!(shown(toasts), vec![b, sidebar]);
+        assert_eq!(toasts.history()[0].id, a);
+    }
+
+    fn toast(toasts: Toasts, priority: Priority) -> Toast {
+        let options = ToastOptions {
+            priority,
+            ..ToastOptions::default()
+        };
+        toasts.new_toast(ViewFn::from(|| "Saved"), None, options)
+    }
+
+    #[test]
+    fn eviction_victim_has_the_lowest_priority() {
+        let toasts = Toasts::new();
+        // Newest first, like the toasts are kept
+        let stack = [
+            toast(toasts, Priority::High),
+            toast(toasts, Priority::Low),
+            toast(toasts, Priority::Normal),
+            toast(toasts, Priority::Low),
+        ];
+        let new = toast(toasts, Priority::Normal);
+        let victim = |policy| eviction_victim(&new, stack.iter(), policy);
+        assert_eq!(victim(EvictionPolicy::DropOldest), Some(stack[3].id));
+        assert_eq!(victim(EvictionPolicy::DropNewest), Some(stack[1].id));
+
+        // A new toast with the lowest priority of all goes first, whatever the policy
+        let new = toast(toasts, Priority::Low);
+        let victim = |policy| eviction_victim(&new, std::iter::empty(), policy);
+        assert_eq!(victim(EvictionPolicy::DropOldest), Some(new.id));
+    }
+
+    #[test]
+    fn critical_toasts_are_never_evicted() {
+        let toasts = Toasts::new();
+        let stack = [
+            toast(toasts, Priority::Critical),
+            toast(toasts, Priority::Critical),
+        ];
+        let new = toast(toasts, Priority::Normal);
+        for policy in [EvictionPolicy::DropOldest, EvictionPolicy::DropNewest] {
+            assert_eq!(eviction_victim(&new, stack.iter(), policy), Some(new.id));
+        }
+        let new = toast(toasts, Priority::Critical);
+        assert_eq!(
+            eviction_victim(&new, stack.iter(), EvictionPolicy::DropOldest),
+            None
+        );
+    }
+
+    #[test]
+    fn critical_toasts_go_over_the_limit() {
+        let toasts = Toasts::new();
+        toasts.set_max_toasts(None, Some(1), EvictionPolicy::Queue);
+        let normal = show(toasts);
+        let critical = toasts
+            .build(|| "Disk full")
+            .priority(Priority::Critical)
+            .show()
+            .id();
+        assert_eq!(shown(toasts), vec![critical, normal]);
+    }
+
+    #[test]
+    fn queued_toasts_are_shown_highest_priority_first() {
+        let toasts = Toasts::new();
+        toasts.set_max_toasts(None, Some(1), EvictionPolicy::Queue);
+        let first = show(toasts);
+        let low = toasts.build(|| "Tip").priority(Priority::Low).show().id();
+        let high = toasts
+            .build(|| "Error")
+            .priority(Priority::High)
+            .show()
+            .id();
+
+        toasts.dismiss(&first);
+        assert_eq!(shown(toasts), vec![high]);
+        toasts.dismiss(&high);
+        assert_eq!(shown(toasts), vec![low]);
+    }
+
+    #[test]
+    fn dismissals_are_emitted_with_the_reason() {
+        let toasts = Toasts::new();
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        toasts.on_event(move |event| recorded.lock().unwrap().push(event.clone()));
+        toasts.set_max_toasts(None, Some(1), EvictionPolicy::DropOldest);
+        toasts.mute_tag("marketing");
+
+        let dismissed = show(toasts);
+        toasts.dismiss(&dismissed);
+        let evicted = show(toasts);
+        show(toasts);
+        let muted = toasts.build(|| "Sale").tag("marketing").show().id();
+
+        let dismissals = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                ToastEvent::Dismissed { id, reason } => Some((*id, *reason)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dismissals,
+            vec![
+                (dismissed, DismissReason::Dismissed),
+                (evicted, DismissReason::Evicted),
+                (muted, DismissReason::Muted),
+            ]
+        );
+    }
+}